@@ -30,8 +30,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Load configuration from environment variables
     println!("Loading OCI configuration from environment variables...");
     let config = OciConfig::from_env()?;
-    let tenancy_id = config.tenancy_id.clone();
-    let compartment_id = std::env::var("OCI_COMPARTMENT_ID").unwrap_or_else(|_| tenancy_id.clone());
 
     // Create OCI HTTP client
     println!("Creating OCI HTTP client...");
@@ -44,7 +42,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Get approved senders
     println!("Fetching approved senders...");
     let senders = email_client
-        .list_senders(&compartment_id, Some("ACTIVE"), None)
+        .list_senders_default(Some("ACTIVE"), None)
         .await?;
 
     if senders.is_empty() {
@@ -121,12 +119,12 @@ You can unsubscribe at any time.
             println!("✅ HTML email sent successfully!");
             println!("Message ID: {}", response.message_id);
             println!("Envelope ID: {}", response.envelope_id);
-            if let Some(suppressed) = response.suppressed_recipients {
-                if !suppressed.is_empty() {
-                    println!("⚠️  Suppressed recipients:");
-                    for recipient in suppressed {
-                        println!("  - {}", recipient.email);
-                    }
+            if let Some(suppressed) = response.suppressed_recipients
+                && !suppressed.is_empty()
+            {
+                println!("⚠️  Suppressed recipients:");
+                for recipient in suppressed {
+                    println!("  - {}", recipient.email);
                 }
             }
         }
@@ -13,8 +13,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Load configuration
     let config = OciConfig::from_env()?;
-    let tenancy_id = config.tenancy_id.clone();
-    let compartment_id = std::env::var("OCI_COMPARTMENT_ID").unwrap_or_else(|_| tenancy_id.clone());
 
     // Create clients
     let oci_client = OciClient::new(&config)?;
@@ -23,7 +21,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Get approved senders
     println!("📋 Fetching approved senders...");
     let senders = email_client
-        .list_senders(&compartment_id, Some("ACTIVE"), None)
+        .list_senders_default(Some("ACTIVE"), None)
         .await?;
 
     if senders.is_empty() {
@@ -103,12 +101,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             println!("📨 Message ID: {}", response.message_id);
             println!("📮 Envelope ID: {}", response.envelope_id);
 
-            if let Some(suppressed) = response.suppressed_recipients {
-                if !suppressed.is_empty() {
-                    println!("\n⚠️  Note: Some recipients were suppressed:");
-                    for recipient in suppressed {
-                        println!("   - {}", recipient.email);
-                    }
+            if let Some(suppressed) = response.suppressed_recipients
+                && !suppressed.is_empty()
+            {
+                println!("\n⚠️  Note: Some recipients were suppressed:");
+                for recipient in suppressed {
+                    println!("   - {}", recipient.email);
                 }
             }
 
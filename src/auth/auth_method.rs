@@ -0,0 +1,311 @@
+//! Authentication method selection
+//!
+//! Selects the authentication strategy from the `OCI_AUTH_METHOD` environment variable,
+//! mirroring the `--auth` option of the OCI CLI.
+
+use crate::auth::AuthProvider;
+use crate::error::{OciError, Result};
+use std::fmt;
+use std::str::FromStr;
+
+/// Authentication method
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AuthMethod {
+    /// API signing key (user OCID, fingerprint and private key)
+    #[default]
+    ApiKey,
+    /// Instance principal (OCI compute instances)
+    InstancePrincipal,
+    /// Resource principal (OCI Functions and other OCI resources)
+    ResourcePrincipal,
+    /// Session token (from `oci session authenticate`)
+    SecurityToken,
+}
+
+impl AuthMethod {
+    /// Read authentication method from `OCI_AUTH_METHOD` (defaults to `api_key` if not set)
+    pub fn from_env() -> Result<Self> {
+        match std::env::var("OCI_AUTH_METHOD") {
+            Ok(value) if !value.trim().is_empty() => value.parse(),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    /// Build the provider of this authentication method from the environment
+    ///
+    /// - `ApiKey`: `AuthProvider::api_key_from_env`
+    /// - `InstancePrincipal`: `AuthProvider::instance_principal` (`OCI_COMPARTMENT_ID`
+    ///   optionally sets the compartment)
+    /// - `ResourcePrincipal`: `AuthProvider::resource_principal_from_env`
+    /// - `SecurityToken`: `AuthProvider::security_token_from_env`
+    pub async fn provider_from_env(&self) -> Result<AuthProvider> {
+        match self {
+            AuthMethod::ApiKey => AuthProvider::api_key_from_env(),
+            AuthMethod::InstancePrincipal => {
+                let mut provider = AuthProvider::instance_principal().await?;
+                provider.config.compartment_id = std::env::var("OCI_COMPARTMENT_ID").ok();
+                Ok(provider)
+            }
+            AuthMethod::ResourcePrincipal => AuthProvider::resource_principal_from_env(),
+            AuthMethod::SecurityToken => AuthProvider::security_token_from_env(),
+        }
+    }
+
+    /// Build the provider of the authentication method selected by `OCI_AUTH_METHOD`
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use oci_api::auth::AuthMethod;
+    /// # async fn example() -> oci_api::Result<()> {
+    /// let client = AuthMethod::load_from_env().await?.client()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn load_from_env() -> Result<AuthProvider> {
+        Self::from_env()?.provider_from_env().await
+    }
+
+    /// `OCI_AUTH_METHOD` value (e.g., `api_key`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuthMethod::ApiKey => "api_key",
+            AuthMethod::InstancePrincipal => "instance_principal",
+            AuthMethod::ResourcePrincipal => "resource_principal",
+            AuthMethod::SecurityToken => "security_token",
+        }
+    }
+}
+
+impl fmt::Display for AuthMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AuthMethod {
+    type Err = OciError;
+
+    /// Parse auth method (case-insensitive, `-` and `_` are interchangeable)
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().replace('-', "_").as_str() {
+            "api_key" => Ok(AuthMethod::ApiKey),
+            "instance_principal" => Ok(AuthMethod::InstancePrincipal),
+            "resource_principal" => Ok(AuthMethod::ResourcePrincipal),
+            "security_token" => Ok(AuthMethod::SecurityToken),
+            _ => Err(OciError::EnvError(format!(
+                "Unknown OCI_AUTH_METHOD '{}' (expected api_key, instance_principal, resource_principal or security_token)",
+                s
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::ENV_LOCK;
+    use crate::client::test_utils::{TEST_KEY, test_token};
+    use std::io::Write;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+    /// Variables read by the providers
+    const AUTH_ENV: &[&str] = &[
+        "OCI_AUTH_METHOD",
+        "OCI_CONFIG",
+        "OCI_USER_ID",
+        "OCI_TENANCY_ID",
+        "OCI_REGION",
+        "OCI_CLI_REGION",
+        "OCI_FINGERPRINT",
+        "OCI_PRIVATE_KEY",
+        "OCI_COMPARTMENT_ID",
+        "OCI_SECURITY_TOKEN",
+        "OCI_SECURITY_TOKEN_FILE",
+        "OCI_RESOURCE_PRINCIPAL_VERSION",
+        "OCI_RESOURCE_PRINCIPAL_RPST",
+        "OCI_RESOURCE_PRINCIPAL_PRIVATE_PEM",
+        "OCI_RESOURCE_PRINCIPAL_PRIVATE_PEM_PASSPHRASE",
+        "OCI_RESOURCE_PRINCIPAL_REGION",
+    ];
+
+    /// Run `future` to completion on a new runtime
+    ///
+    /// The tests are synchronous so that the environment lock is not held across `.await`.
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    /// Set `vars` (after clearing the other auth variables) and load the provider
+    fn load_with_env(vars: &[(&str, &str)]) -> Result<AuthProvider> {
+        let _guard = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        unsafe {
+            for name in AUTH_ENV {
+                std::env::remove_var(name);
+            }
+            for (name, value) in vars {
+                std::env::set_var(name, value);
+            }
+        }
+
+        let result = block_on(AuthMethod::load_from_env());
+
+        unsafe {
+            for (name, _) in vars {
+                std::env::remove_var(name);
+            }
+        }
+        result
+    }
+
+    /// Assert that a request sent by a client of `provider` is signed with `key_id`
+    fn assert_signs_with(provider: AuthProvider, key_id: &str) {
+        let expected = format!("keyId=\"{}\"", key_id);
+        block_on(async {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path("/test"))
+                .and(move |request: &Request| {
+                    request
+                        .headers
+                        .get("authorization")
+                        .and_then(|value| value.to_str().ok())
+                        .is_some_and(|value| value.contains(&expected))
+                })
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            let client = provider.client().unwrap();
+            let _: serde_json::Value = client
+                .get_json("identity", &server.uri(), "/test", &[])
+                .await
+                .unwrap();
+        });
+    }
+
+    #[test]
+    fn test_parse_auth_method() {
+        assert_eq!("api_key".parse::<AuthMethod>().unwrap(), AuthMethod::ApiKey);
+        assert_eq!(
+            "Instance-Principal".parse::<AuthMethod>().unwrap(),
+            AuthMethod::InstancePrincipal
+        );
+        assert_eq!(
+            "resource_principal".parse::<AuthMethod>().unwrap(),
+            AuthMethod::ResourcePrincipal
+        );
+        assert_eq!(
+            "SECURITY_TOKEN".parse::<AuthMethod>().unwrap(),
+            AuthMethod::SecurityToken
+        );
+        assert!(matches!(
+            "password".parse::<AuthMethod>(),
+            Err(OciError::EnvError(_))
+        ));
+    }
+
+    #[test]
+    fn test_dispatch_api_key_from_env() {
+        let api_key_env = [
+            ("OCI_USER_ID", "ocid1.user.test"),
+            ("OCI_TENANCY_ID", "ocid1.tenancy.test"),
+            ("OCI_REGION", "ap-seoul-1"),
+            ("OCI_FINGERPRINT", "aa:bb:cc:dd"),
+            ("OCI_PRIVATE_KEY", TEST_KEY),
+        ];
+
+        // Defaults to api_key when OCI_AUTH_METHOD is not set
+        let provider = load_with_env(&api_key_env).unwrap();
+        assert_eq!(provider.config.user_id, "ocid1.user.test");
+
+        let provider =
+            load_with_env(&[&api_key_env[..], &[("OCI_AUTH_METHOD", "api_key")]].concat()).unwrap();
+        assert_eq!(provider.config.region, "ap-seoul-1");
+        assert_signs_with(provider, "ocid1.tenancy.test/ocid1.user.test/aa:bb:cc:dd");
+    }
+
+    #[test]
+    fn test_dispatch_security_token_from_env() {
+        let token = test_token(serde_json::json!({"tenant": "ocid1.tenancy.oc1..session"}));
+        let mut token_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(token_file, "{}", token).unwrap();
+
+        let provider = load_with_env(&[
+            ("OCI_AUTH_METHOD", "security_token"),
+            (
+                "OCI_SECURITY_TOKEN_FILE",
+                token_file.path().to_str().unwrap(),
+            ),
+            ("OCI_PRIVATE_KEY", TEST_KEY),
+            ("OCI_CLI_REGION", "us-ashburn-1"),
+        ])
+        .unwrap();
+
+        // Tenancy defaults to the tenant claim of the token
+        assert_eq!(provider.config.tenancy_id, "ocid1.tenancy.oc1..session");
+        assert_eq!(provider.config.region, "us-ashburn-1");
+        assert_eq!(provider.config.user_id, "");
+        assert_signs_with(provider, &format!("ST${}", token));
+
+        match load_with_env(&[
+            ("OCI_AUTH_METHOD", "security_token"),
+            ("OCI_PRIVATE_KEY", TEST_KEY),
+            ("OCI_REGION", "us-ashburn-1"),
+        ]) {
+            Err(OciError::EnvError(msg)) => assert!(msg.contains("OCI_SECURITY_TOKEN")),
+            other => panic!("Expected EnvError, got: {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_dispatch_resource_principal_from_env() {
+        let token = test_token(serde_json::json!({
+            "res_tenant": "ocid1.tenancy.oc1..fn",
+            "res_compartment": "ocid1.compartment.oc1..fn",
+        }));
+        let mut token_file = tempfile::NamedTempFile::new().unwrap();
+        write!(token_file, "{}", token).unwrap();
+
+        let provider = load_with_env(&[
+            ("OCI_AUTH_METHOD", "resource_principal"),
+            ("OCI_RESOURCE_PRINCIPAL_VERSION", "2.2"),
+            (
+                "OCI_RESOURCE_PRINCIPAL_RPST",
+                token_file.path().to_str().unwrap(),
+            ),
+            ("OCI_RESOURCE_PRINCIPAL_PRIVATE_PEM", TEST_KEY),
+            ("OCI_RESOURCE_PRINCIPAL_REGION", "ap-seoul-1"),
+        ])
+        .unwrap();
+
+        assert_eq!(provider.config.tenancy_id, "ocid1.tenancy.oc1..fn");
+        assert_eq!(
+            provider.config.compartment_id.as_deref(),
+            Some("ocid1.compartment.oc1..fn")
+        );
+        assert_signs_with(provider, &format!("ST${}", token));
+
+        match load_with_env(&[
+            ("OCI_AUTH_METHOD", "resource_principal"),
+            ("OCI_RESOURCE_PRINCIPAL_VERSION", "1.1"),
+        ]) {
+            Err(OciError::AuthError(msg)) => assert!(msg.contains("1.1")),
+            other => panic!("Expected AuthError, got: {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_dispatch_unknown_method() {
+        assert!(matches!(
+            load_with_env(&[("OCI_AUTH_METHOD", "unknown")]),
+            Err(OciError::EnvError(_))
+        ));
+    }
+}
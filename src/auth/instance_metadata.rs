@@ -0,0 +1,142 @@
+//! OCI instance metadata service client
+//!
+//! Reads instance information (e.g. region, identity certificates) from the metadata
+//! endpoint that is only reachable from inside an OCI compute instance.
+
+use crate::error::{OciError, Result};
+use reqwest::Client;
+use std::time::Duration;
+
+/// Default instance metadata endpoint (link-local address)
+const DEFAULT_METADATA_ENDPOINT: &str = "http://169.254.169.254";
+
+/// Timeout for metadata requests (kept short to fail fast outside of OCI)
+const METADATA_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Instance metadata service (IMDS v2) client
+pub struct InstanceMetadata {
+    /// HTTP client with short timeouts
+    client: Client,
+
+    /// Metadata endpoint base URL
+    endpoint: String,
+}
+
+impl InstanceMetadata {
+    /// Create metadata client for the default endpoint (`http://169.254.169.254`)
+    pub fn new() -> Result<Self> {
+        Self::with_endpoint(DEFAULT_METADATA_ENDPOINT)
+    }
+
+    /// Create metadata client for a custom endpoint (useful for proxies and tests)
+    pub fn with_endpoint(endpoint: impl Into<String>) -> Result<Self> {
+        let client = Client::builder()
+            .connect_timeout(METADATA_TIMEOUT)
+            .timeout(METADATA_TIMEOUT)
+            .build()?;
+
+        Ok(Self {
+            client,
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Get the canonical region name of the current instance (e.g., `ap-seoul-1`)
+    ///
+    /// Fails fast with `OciError::ConfigError` when the metadata service is not
+    /// reachable, which usually means the process is not running on OCI compute.
+    pub async fn region(&self) -> Result<String> {
+        let region = self.get("/opc/v2/instance/canonicalRegionName").await?;
+        let region = region.trim();
+
+        if region.is_empty() {
+            return Err(OciError::ConfigError(
+                "Instance metadata returned an empty region".to_string(),
+            ));
+        }
+
+        Ok(region.to_string())
+    }
+
+    /// Get the identity certificates of the current instance
+    ///
+    /// The leaf certificate and its key are what an instance principal federates with.
+    pub(crate) async fn identity(&self) -> Result<InstanceIdentity> {
+        Ok(InstanceIdentity {
+            certificate: self.get("/opc/v2/identity/cert.pem").await?,
+            private_key: self.get("/opc/v2/identity/key.pem").await?,
+            intermediate_certificate: self.get("/opc/v2/identity/intermediate.pem").await?,
+        })
+    }
+
+    /// Execute GET request against the metadata service and return the body text
+    async fn get(&self, path: &str) -> Result<String> {
+        let url = format!("{}{}", self.endpoint, path);
+
+        // IMDS v2 requires the `Authorization: Bearer Oracle` header
+        let response = self
+            .client
+            .get(&url)
+            .header("authorization", "Bearer Oracle")
+            .send()
+            .await
+            .map_err(|e| {
+                OciError::ConfigError(format!(
+                    "Instance metadata service is not reachable at {} (not running on OCI compute?): {}",
+                    self.endpoint, e
+                ))
+            })?;
+
+        if !response.status().is_success() {
+            return Err(OciError::ConfigError(format!(
+                "Instance metadata request failed with status {}",
+                response.status()
+            )));
+        }
+
+        Ok(response.text().await?)
+    }
+}
+
+/// Identity certificates of an instance (PEM)
+pub(crate) struct InstanceIdentity {
+    /// Leaf certificate (subject names the tenancy)
+    pub certificate: String,
+
+    /// Private key of the leaf certificate
+    pub private_key: String,
+
+    /// Intermediate CA certificate
+    pub intermediate_certificate: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_region_from_metadata() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/opc/v2/instance/canonicalRegionName"))
+            .and(header("authorization", "Bearer Oracle"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ap-seoul-1\n"))
+            .mount(&server)
+            .await;
+
+        let metadata = InstanceMetadata::with_endpoint(server.uri()).unwrap();
+        assert_eq!(metadata.region().await.unwrap(), "ap-seoul-1");
+    }
+
+    #[tokio::test]
+    async fn test_region_not_on_oci() {
+        // Nothing listens on port 1, so the connection is refused immediately
+        let metadata = InstanceMetadata::with_endpoint("http://127.0.0.1:1").unwrap();
+        match metadata.region().await.unwrap_err() {
+            OciError::ConfigError(msg) => assert!(msg.contains("not running on OCI")),
+            e => panic!("Expected ConfigError, got: {:?}", e),
+        }
+    }
+}
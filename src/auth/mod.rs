@@ -1,8 +1,20 @@
 // Authentication modules
+pub mod auth_method;
 pub mod config;
 pub mod config_loader;
+pub mod instance_metadata;
 pub mod key_loader;
+pub mod provider;
+pub mod region;
 
+pub use auth_method::AuthMethod;
 pub use config::{OciConfig, OciConfigBuilder};
 pub use config_loader::ConfigLoader;
+pub use instance_metadata::InstanceMetadata;
 pub use key_loader::KeyLoader;
+pub use provider::AuthProvider;
+pub use region::Region;
+
+/// Lock for tests that modify process environment variables
+#[cfg(test)]
+pub(crate) static ENV_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
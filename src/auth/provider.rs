@@ -0,0 +1,540 @@
+//! Authentication providers
+//!
+//! Builds the configuration and request signer of each authentication method
+//! (see `AuthMethod`). Token-based methods sign with a session key and a
+//! `ST${token}` key id instead of a user API key.
+
+use crate::auth::{InstanceMetadata, KeyLoader, OciConfig};
+use crate::client::{
+    OciClient, OciClientBuilder, OciSigner, SigningKeyKind, response_json, split_endpoint,
+};
+use crate::error::{OciError, Result};
+use crate::utils::{base64_decode, body_sha256_base64};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use reqwest::Client;
+use rsa::pkcs1::DecodeRsaPrivateKey;
+use rsa::pkcs8::{DecodePrivateKey, EncodePublicKey, LineEnding};
+use rsa::{RsaPrivateKey, RsaPublicKey};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::time::Duration;
+
+/// Timeout for the instance principal token request
+const FEDERATION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Size of the session key generated for instance principals
+const SESSION_KEY_BITS: usize = 2048;
+
+/// Configuration and request signer of an authentication method
+///
+/// For token-based methods, `config` only carries the tenancy, region and compartment:
+/// `user_id`, `fingerprint` and `private_key` are empty, and requests are signed by `signer`.
+///
+/// Security tokens expire (instance principal tokens after about 20 minutes) and are not
+/// renewed automatically: build a new provider and pass its signer to
+/// `OciClient::replace_signer`.
+///
+/// # Example
+/// ```no_run
+/// # use oci_api::auth::AuthProvider;
+/// # async fn example() -> oci_api::Result<()> {
+/// let client = AuthProvider::instance_principal().await?.client()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct AuthProvider {
+    /// Configuration (tenancy, region, compartment)
+    pub config: OciConfig,
+
+    /// Request signer
+    pub signer: OciSigner,
+}
+
+impl AuthProvider {
+    /// API signing key from environment variables (see `OciConfig::from_env`)
+    pub fn api_key_from_env() -> Result<Self> {
+        let config = OciConfig::from_env()?;
+        let signer = OciSigner::new(&config)?;
+        Ok(Self { config, signer })
+    }
+
+    /// Session token from environment variables (from `oci session authenticate`)
+    ///
+    /// # Environment Variables
+    /// - `OCI_SECURITY_TOKEN`: Session token, or `OCI_SECURITY_TOKEN_FILE`: token file path
+    ///   (e.g., `~/.oci/sessions/DEFAULT/token`)
+    /// - `OCI_PRIVATE_KEY`: Session private key file path or PEM content
+    /// - `OCI_REGION`: Region (falls back to `OCI_CLI_REGION`)
+    /// - `OCI_TENANCY_ID`: Tenancy ID (optional, defaults to the `tenant` claim of the token)
+    /// - `OCI_COMPARTMENT_ID`: Compartment ID (optional, defaults to tenancy_id)
+    pub fn security_token_from_env() -> Result<Self> {
+        let token = match (
+            env_var("OCI_SECURITY_TOKEN"),
+            env_var("OCI_SECURITY_TOKEN_FILE"),
+        ) {
+            (Some(token), _) => token,
+            (None, Some(path)) => read_token_file(&expand_home(&path))?,
+            (None, None) => {
+                return Err(OciError::EnvError(
+                    "OCI_SECURITY_TOKEN or OCI_SECURITY_TOKEN_FILE must be set".to_string(),
+                ));
+            }
+        };
+
+        let private_key = env_var("OCI_PRIVATE_KEY").ok_or_else(|| {
+            OciError::EnvError("OCI_PRIVATE_KEY must be set (session private key)".to_string())
+        })?;
+        let key = parse_private_key(&KeyLoader::load(&expand_home(&private_key))?)?;
+
+        let region = env_var("OCI_REGION")
+            .or_else(|| env_var("OCI_CLI_REGION"))
+            .ok_or_else(|| {
+                OciError::EnvError("OCI_REGION (or OCI_CLI_REGION) must be set".to_string())
+            })?;
+
+        let tenancy_id = match env_var("OCI_TENANCY_ID") {
+            Some(tenancy_id) => tenancy_id,
+            None => token_claim(&token, "tenant")?.ok_or_else(|| {
+                OciError::EnvError(
+                    "OCI_TENANCY_ID must be set (the security token has no tenant claim)"
+                        .to_string(),
+                )
+            })?,
+        };
+
+        Ok(Self::from_token(
+            token_config(tenancy_id, region, env_var("OCI_COMPARTMENT_ID")),
+            key,
+            &token,
+        ))
+    }
+
+    /// Resource principal (v2.2) from the environment variables set by OCI Functions
+    ///
+    /// # Environment Variables
+    /// - `OCI_RESOURCE_PRINCIPAL_VERSION`: Must be `2.2`
+    /// - `OCI_RESOURCE_PRINCIPAL_RPST`: Resource principal session token, or absolute path
+    ///   of the file containing it
+    /// - `OCI_RESOURCE_PRINCIPAL_PRIVATE_PEM`: Session private key PEM, or absolute path of
+    ///   the file containing it
+    /// - `OCI_RESOURCE_PRINCIPAL_REGION`: Region
+    /// - `OCI_COMPARTMENT_ID`: Compartment ID (optional, defaults to the `res_compartment`
+    ///   claim of the token)
+    ///
+    /// The tenancy is the `res_tenant` claim of the token. Encrypted private keys
+    /// (`OCI_RESOURCE_PRINCIPAL_PRIVATE_PEM_PASSPHRASE`) are not supported.
+    pub fn resource_principal_from_env() -> Result<Self> {
+        match env_var("OCI_RESOURCE_PRINCIPAL_VERSION").as_deref() {
+            Some("2.2") => {}
+            Some(version) => {
+                return Err(OciError::AuthError(format!(
+                    "Resource principal version '{}' is not supported (expected 2.2)",
+                    version
+                )));
+            }
+            None => {
+                return Err(OciError::EnvError(
+                    "OCI_RESOURCE_PRINCIPAL_VERSION must be set (not running as a resource principal?)"
+                        .to_string(),
+                ));
+            }
+        }
+
+        if env_var("OCI_RESOURCE_PRINCIPAL_PRIVATE_PEM_PASSPHRASE").is_some() {
+            return Err(OciError::AuthError(
+                "Encrypted resource principal keys are not supported".to_string(),
+            ));
+        }
+
+        let required = |name: &str| {
+            env_var(name).ok_or_else(|| OciError::EnvError(format!("{} must be set", name)))
+        };
+        let token = value_or_file(&required("OCI_RESOURCE_PRINCIPAL_RPST")?)?;
+        let key = parse_private_key(&value_or_file(&required(
+            "OCI_RESOURCE_PRINCIPAL_PRIVATE_PEM",
+        )?)?)?;
+        let region = required("OCI_RESOURCE_PRINCIPAL_REGION")?;
+
+        let tenancy_id = token_claim(&token, "res_tenant")?.ok_or_else(|| {
+            OciError::AuthError("Resource principal token has no res_tenant claim".to_string())
+        })?;
+        let compartment_id =
+            env_var("OCI_COMPARTMENT_ID").or(token_claim(&token, "res_compartment")?);
+
+        Ok(Self::from_token(
+            token_config(tenancy_id, region, compartment_id),
+            key,
+            &token,
+        ))
+    }
+
+    /// Instance principal of the current OCI compute instance
+    ///
+    /// Reads the region and identity certificates from instance metadata and exchanges
+    /// them for a security token at `auth.{region}.oraclecloud.com`. Fails fast with
+    /// `OciError::ConfigError` when not running on OCI compute.
+    pub async fn instance_principal() -> Result<Self> {
+        Self::instance_principal_with(&InstanceMetadata::new()?, None).await
+    }
+
+    /// Instance principal with a custom metadata client and auth endpoint (useful for tests)
+    ///
+    /// # Arguments
+    /// * `metadata` - Instance metadata client
+    /// * `federation_endpoint` - Auth service host or base URL (defaults to
+    ///   `auth.{region}.oraclecloud.com`)
+    pub async fn instance_principal_with(
+        metadata: &InstanceMetadata,
+        federation_endpoint: Option<&str>,
+    ) -> Result<Self> {
+        let region = metadata.region().await?;
+        let identity = metadata.identity().await?;
+
+        let certificate = pem_body(&identity.certificate);
+        let certificate_der = base64_decode(&certificate)?;
+        let tenancy_id = certificate_tenancy(&certificate_der)?;
+        let fingerprint = Sha256::digest(&certificate_der)
+            .iter()
+            .map(|b| format!("{:02X}", b))
+            .collect::<Vec<_>>()
+            .join(":");
+
+        // The token is bound to a fresh session key; the instance key only signs the exchange
+        let session_key = RsaPrivateKey::new(&mut rand::rngs::OsRng, SESSION_KEY_BITS)
+            .map_err(|e| OciError::KeyError(format!("Failed to generate session key: {}", e)))?;
+        let public_key = RsaPublicKey::from(&session_key)
+            .to_public_key_pem(LineEnding::LF)
+            .map_err(|e| OciError::KeyError(format!("Failed to encode session key: {}", e)))?;
+
+        let body = serde_json::json!({
+            "certificate": certificate,
+            "publicKey": pem_body(&public_key),
+            "intermediateCertificates": [pem_body(&identity.intermediate_certificate)],
+            "purpose": "DEFAULT",
+            "fingerprintAlgorithm": "SHA256",
+        })
+        .to_string();
+
+        let instance_signer = OciSigner::from_parts(
+            parse_private_key(&identity.private_key)?,
+            format!("{}/fed-x509-sha256/{}", tenancy_id, fingerprint),
+        );
+        let endpoint = match federation_endpoint {
+            Some(endpoint) => endpoint.to_string(),
+            None => format!("auth.{}.oraclecloud.com", region),
+        };
+        let token = federate(&instance_signer, &endpoint, &body).await?;
+
+        Ok(Self::from_token(
+            token_config(tenancy_id, region, None),
+            session_key.into(),
+            &token,
+        ))
+    }
+
+    /// Client builder signing with this provider
+    pub fn client_builder(self) -> OciClientBuilder {
+        OciClient::builder(&self.config).signer(self.signer)
+    }
+
+    /// Client signing with this provider
+    pub fn client(self) -> Result<OciClient> {
+        self.client_builder().build()
+    }
+
+    /// Provider signing with `key` and the `ST${token}` key id
+    fn from_token(config: OciConfig, key: SigningKeyKind, token: &str) -> Self {
+        Self {
+            config,
+            signer: OciSigner::from_parts(key, format!("ST${}", token)),
+        }
+    }
+}
+
+/// `token` response of the auth service
+#[derive(Deserialize)]
+struct FederationResponse {
+    token: String,
+}
+
+/// Exchange the instance certificates in `body` for a security token
+async fn federate(signer: &OciSigner, endpoint: &str, body: &str) -> Result<String> {
+    let (scheme, host) = split_endpoint(endpoint);
+    let path = "/v1/x509";
+    let content_type = "application/json";
+    let (date, authorization) =
+        signer.sign_request_with_content_type("POST", path, host, Some(body), content_type)?;
+
+    let response = Client::builder()
+        .timeout(FEDERATION_TIMEOUT)
+        .build()?
+        .post(format!("{}://{}{}", scheme, host, path))
+        .header("date", date)
+        .header("authorization", authorization)
+        .header("content-type", content_type)
+        .header("x-content-sha256", body_sha256_base64(body.as_bytes()))
+        .body(body.to_string())
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(OciError::from_response(response).await);
+    }
+
+    let response: FederationResponse = response_json(response).await?;
+    Ok(response.token)
+}
+
+/// Configuration of a token-based provider (no user, fingerprint or API key)
+fn token_config(tenancy_id: String, region: String, compartment_id: Option<String>) -> OciConfig {
+    OciConfig {
+        user_id: String::new(),
+        tenancy_id,
+        region,
+        fingerprint: String::new(),
+        private_key: String::new(),
+        compartment_id,
+    }
+}
+
+/// Non-empty environment variable
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+}
+
+/// Expand a leading `~/` to the home directory
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) => format!("{}/{}", home, rest),
+        _ => path.to_string(),
+    }
+}
+
+/// Read a security token file
+fn read_token_file(path: &str) -> Result<String> {
+    std::fs::read_to_string(path)
+        .map(|token| token.trim().to_string())
+        .map_err(|e| {
+            OciError::AuthError(format!(
+                "Failed to read security token file {}: {}",
+                path, e
+            ))
+        })
+}
+
+/// Value, or the content of the file if it is an absolute path (as the OCI SDKs do)
+fn value_or_file(value: &str) -> Result<String> {
+    if Path::new(value).is_absolute() {
+        read_token_file(value)
+    } else {
+        Ok(value.trim().to_string())
+    }
+}
+
+/// Parse an RSA private key PEM (PKCS#8 or PKCS#1, as served by instance metadata)
+fn parse_private_key(pem: &str) -> Result<SigningKeyKind> {
+    let pem = pem.trim();
+    let key = RsaPrivateKey::from_pkcs8_pem(pem)
+        .or_else(|_| RsaPrivateKey::from_pkcs1_pem(pem))
+        .map_err(|e| OciError::KeyError(format!("Invalid private key: {}", e)))?;
+    Ok(key.into())
+}
+
+/// Claim of a JWT security token (`None` if the claim is missing)
+fn token_claim(token: &str, claim: &str) -> Result<Option<String>> {
+    let invalid = || OciError::AuthError("Security token is not a valid JWT".to_string());
+
+    let payload = token.split('.').nth(1).ok_or_else(invalid)?;
+    let payload = URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .map_err(|_| invalid())?;
+    let claims: serde_json::Value = serde_json::from_slice(&payload).map_err(|_| invalid())?;
+
+    Ok(claims
+        .get(claim)
+        .and_then(|value| value.as_str())
+        .map(str::to_string))
+}
+
+/// Base64 body of a PEM block, without the armor lines and line breaks
+fn pem_body(pem: &str) -> String {
+    pem.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("-----"))
+        .collect()
+}
+
+/// Tenancy OCID from the `opc-tenant:{ocid}` subject name of an instance certificate (DER)
+fn certificate_tenancy(der: &[u8]) -> Result<String> {
+    const PREFIX: &[u8] = b"opc-tenant:";
+    let missing = || {
+        OciError::AuthError("Instance certificate does not name a tenancy (opc-tenant)".to_string())
+    };
+
+    // The prefix starts the content of a DER string; its length precedes it
+    let start = der
+        .windows(PREFIX.len())
+        .position(|window| window == PREFIX)
+        .ok_or_else(missing)?;
+    let len = match der[..start] {
+        [.., 0x81, len] => len as usize,
+        [.., len] if len < 0x80 => len as usize,
+        _ => return Err(missing()),
+    };
+    let value = der.get(start..start + len).ok_or_else(missing)?;
+    let tenancy = value.strip_prefix(PREFIX).ok_or_else(missing)?;
+
+    std::str::from_utf8(tenancy)
+        .ok()
+        .filter(|tenancy| tenancy.starts_with("ocid1.tenancy."))
+        .map(str::to_string)
+        .ok_or_else(missing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_utils::{TEST_KEY, test_token};
+    use crate::utils::base64_encode;
+    use wiremock::matchers::{body_partial_json, header, header_regex, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Certificate PEM whose DER holds an `opc-tenant` subject name
+    fn test_certificate(tenancy_id: &str) -> String {
+        let value = format!("opc-tenant:{}", tenancy_id);
+        let mut der = vec![0x31, 0x30, 0x06, 0x03, 0x55, 0x04, 0x0b, 0x0c];
+        der.push(value.len() as u8);
+        der.extend_from_slice(value.as_bytes());
+        der.extend_from_slice(&[0x31, 0x30]);
+        format!(
+            "-----BEGIN CERTIFICATE-----\n{}\n-----END CERTIFICATE-----\n",
+            base64_encode(der)
+        )
+    }
+
+    /// Metadata server of an instance in `ap-seoul-1` of tenancy `ocid1.tenancy.oc1..test`
+    async fn metadata_server() -> MockServer {
+        let server = MockServer::start().await;
+        for (route, body) in [
+            (
+                "/opc/v2/instance/canonicalRegionName",
+                "ap-seoul-1".to_string(),
+            ),
+            (
+                "/opc/v2/identity/cert.pem",
+                test_certificate("ocid1.tenancy.oc1..test"),
+            ),
+            ("/opc/v2/identity/key.pem", TEST_KEY.to_string()),
+            (
+                "/opc/v2/identity/intermediate.pem",
+                test_certificate("ocid1.tenancy.oc1..ca"),
+            ),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(route))
+                .and(header("authorization", "Bearer Oracle"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(body))
+                .mount(&server)
+                .await;
+        }
+        server
+    }
+
+    #[test]
+    fn test_token_claim() {
+        let token = test_token(serde_json::json!({"res_tenant": "ocid1.tenancy.oc1..test"}));
+        assert_eq!(
+            token_claim(&token, "res_tenant").unwrap().as_deref(),
+            Some("ocid1.tenancy.oc1..test")
+        );
+        assert_eq!(token_claim(&token, "res_compartment").unwrap(), None);
+        assert!(matches!(
+            token_claim("not-a-jwt", "tenant"),
+            Err(OciError::AuthError(_))
+        ));
+    }
+
+    #[test]
+    fn test_certificate_tenancy() {
+        let pem = test_certificate("ocid1.tenancy.oc1..test");
+        let der = base64_decode(&pem_body(&pem)).unwrap();
+        assert_eq!(
+            certificate_tenancy(&der).unwrap(),
+            "ocid1.tenancy.oc1..test"
+        );
+
+        assert!(matches!(
+            certificate_tenancy(b"\x30\x03abc"),
+            Err(OciError::AuthError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_instance_principal() {
+        let metadata_server = metadata_server().await;
+        let auth_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/x509"))
+            .and(header_regex(
+                "authorization",
+                r#"keyId="ocid1\.tenancy\.oc1\.\.test/fed-x509-sha256/([0-9A-F]{2}:){31}[0-9A-F]{2}""#,
+            ))
+            .and(body_partial_json(serde_json::json!({
+                "purpose": "DEFAULT",
+                "fingerprintAlgorithm": "SHA256",
+            })))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"token": "ip-token"})),
+            )
+            .expect(1)
+            .mount(&auth_server)
+            .await;
+
+        let metadata = InstanceMetadata::with_endpoint(metadata_server.uri()).unwrap();
+        let provider = AuthProvider::instance_principal_with(&metadata, Some(&auth_server.uri()))
+            .await
+            .unwrap();
+        assert_eq!(provider.config.tenancy_id, "ocid1.tenancy.oc1..test");
+        assert_eq!(provider.config.region, "ap-seoul-1");
+        assert_eq!(provider.signer.key_id(), "ST$ip-token");
+
+        // Requests are signed with the session key and the token
+        let api_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/test"))
+            .and(header_regex("authorization", r#"keyId="ST\$ip-token""#))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(1)
+            .mount(&api_server)
+            .await;
+
+        let client = provider.client().unwrap();
+        let _: serde_json::Value = client
+            .get_json("identity", &api_server.uri(), "/test", &[])
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_instance_principal_federation_rejected() {
+        let metadata_server = metadata_server().await;
+        let auth_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/x509"))
+            .respond_with(ResponseTemplate::new(401).set_body_json(serde_json::json!({
+                "code": "NotAuthenticated",
+                "message": "Certificate is not valid",
+            })))
+            .mount(&auth_server)
+            .await;
+
+        let metadata = InstanceMetadata::with_endpoint(metadata_server.uri()).unwrap();
+        let result =
+            AuthProvider::instance_principal_with(&metadata, Some(&auth_server.uri())).await;
+        assert!(matches!(result, Err(OciError::Unauthorized { .. })));
+    }
+}
@@ -0,0 +1,260 @@
+//! OCI regions
+//!
+//! Typed region identifiers, so that typos are caught when the configuration is
+//! loaded instead of at DNS resolution or signing time.
+
+use crate::error::{OciError, Result};
+use std::fmt;
+use std::str::FromStr;
+
+macro_rules! regions {
+    ($($variant:ident => $id:literal,)*) => {
+        /// OCI region
+        ///
+        /// Covers the commercial regions (realm `oc1`). Regions not known to this
+        /// version of the crate are represented as `Other`.
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum Region {
+            $(
+                #[doc = concat!("`", $id, "`")]
+                $variant,
+            )*
+            /// Region not known to this crate (e.g., newly launched or non-commercial realm)
+            Other(String),
+        }
+
+        impl Region {
+            /// All known regions
+            pub const ALL: &'static [Region] = &[$(Region::$variant,)*];
+
+            /// Region identifier (e.g., `ap-seoul-1`)
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Region::$variant => $id,)*
+                    Region::Other(id) => id,
+                }
+            }
+
+            /// Known region for `id`
+            fn from_known_id(id: &str) -> Option<Self> {
+                match id {
+                    $($id => Some(Region::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+regions! {
+    AfJohannesburg1 => "af-johannesburg-1",
+    ApBatam1 => "ap-batam-1",
+    ApChuncheon1 => "ap-chuncheon-1",
+    ApHyderabad1 => "ap-hyderabad-1",
+    ApMelbourne1 => "ap-melbourne-1",
+    ApMumbai1 => "ap-mumbai-1",
+    ApOsaka1 => "ap-osaka-1",
+    ApSeoul1 => "ap-seoul-1",
+    ApSingapore1 => "ap-singapore-1",
+    ApSingapore2 => "ap-singapore-2",
+    ApSydney1 => "ap-sydney-1",
+    ApTokyo1 => "ap-tokyo-1",
+    CaMontreal1 => "ca-montreal-1",
+    CaToronto1 => "ca-toronto-1",
+    EuAmsterdam1 => "eu-amsterdam-1",
+    EuFrankfurt1 => "eu-frankfurt-1",
+    EuJovanovac1 => "eu-jovanovac-1",
+    EuMadrid1 => "eu-madrid-1",
+    EuMarseille1 => "eu-marseille-1",
+    EuMilan1 => "eu-milan-1",
+    EuParis1 => "eu-paris-1",
+    EuStockholm1 => "eu-stockholm-1",
+    EuZurich1 => "eu-zurich-1",
+    IlJerusalem1 => "il-jerusalem-1",
+    MeAbudhabi1 => "me-abudhabi-1",
+    MeDubai1 => "me-dubai-1",
+    MeJeddah1 => "me-jeddah-1",
+    MeRiyadh1 => "me-riyadh-1",
+    MxMonterrey1 => "mx-monterrey-1",
+    MxQueretaro1 => "mx-queretaro-1",
+    SaBogota1 => "sa-bogota-1",
+    SaSantiago1 => "sa-santiago-1",
+    SaSaopaulo1 => "sa-saopaulo-1",
+    SaValparaiso1 => "sa-valparaiso-1",
+    SaVinhedo1 => "sa-vinhedo-1",
+    UkCardiff1 => "uk-cardiff-1",
+    UkLondon1 => "uk-london-1",
+    UsAshburn1 => "us-ashburn-1",
+    UsChicago1 => "us-chicago-1",
+    UsPhoenix1 => "us-phoenix-1",
+    UsSanjose1 => "us-sanjose-1",
+}
+
+impl Region {
+    /// Realm of the region (e.g., `oc1`), `None` for `Other`
+    pub fn realm(&self) -> Option<&'static str> {
+        match self {
+            Region::Other(_) => None,
+            _ => Some("oc1"),
+        }
+    }
+
+    /// Whether the region is known to this crate
+    pub fn is_known(&self) -> bool {
+        !matches!(self, Region::Other(_))
+    }
+
+    /// Region of an OCI service endpoint (`*.{region}.oci.oraclecloud.com`)
+    ///
+    /// Accepts a host or a URL (scheme, port and path are ignored), e.g.
+    /// `https://cell0.submit.email.ap-seoul-1.oci.oraclecloud.com/20220926`. The legacy
+    /// `*.{region}.oraclecloud.com` hosts are accepted too. Returns
+    /// `OciError::ConfigError` for other hosts.
+    pub fn from_endpoint(endpoint: &str) -> Result<Self> {
+        let host = endpoint
+            .trim()
+            .split_once("://")
+            .map_or(endpoint.trim(), |(_, rest)| rest);
+        let host = host.split(['/', '?', '#']).next().unwrap_or_default();
+        let host = host.rsplit_once(':').map_or(host, |(host, _)| host);
+        let host = host.trim_end_matches('.').to_lowercase();
+
+        host.strip_suffix(".oci.oraclecloud.com")
+            .or_else(|| host.strip_suffix(".oraclecloud.com"))
+            .and_then(|rest| rest.rsplit('.').next())
+            .and_then(|region| region.parse().ok())
+            .ok_or_else(|| {
+                OciError::ConfigError(format!(
+                    "Cannot determine region from endpoint '{}' (expected a host like \
+                     '*.ap-seoul-1.oci.oraclecloud.com')",
+                    endpoint
+                ))
+            })
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Region {
+    type Err = OciError;
+
+    /// Parse region identifier (case-insensitive)
+    ///
+    /// Unknown but well-formed identifiers (`{prefix}-{name}-{number}`) become `Other`;
+    /// malformed identifiers are rejected with `OciError::ConfigError`.
+    fn from_str(s: &str) -> Result<Self> {
+        let id = s.trim().to_lowercase();
+
+        if let Some(region) = Region::from_known_id(&id) {
+            return Ok(region);
+        }
+
+        if !is_well_formed(&id) {
+            return Err(OciError::ConfigError(format!(
+                "Invalid region '{}' (expected e.g. 'ap-seoul-1')",
+                s
+            )));
+        }
+
+        Ok(Region::Other(id))
+    }
+}
+
+/// Whether `id` looks like a region identifier (`{prefix}-{name}-{number}`)
+fn is_well_formed(id: &str) -> bool {
+    let parts: Vec<&str> = id.split('-').collect();
+    let Some((number, names)) = parts.split_last() else {
+        return false;
+    };
+
+    names.len() >= 2
+        && names
+            .iter()
+            .all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_lowercase()))
+        && !number.is_empty()
+        && number.chars().all(|c| c.is_ascii_digit())
+        && !number.starts_with('0')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_known_regions() {
+        assert_eq!("ap-seoul-1".parse::<Region>().unwrap(), Region::ApSeoul1);
+        assert_eq!(
+            " US-ASHBURN-1 ".parse::<Region>().unwrap(),
+            Region::UsAshburn1
+        );
+
+        for region in Region::ALL {
+            assert_eq!(&region.as_str().parse::<Region>().unwrap(), region);
+            assert_eq!(region.realm(), Some("oc1"));
+        }
+    }
+
+    #[test]
+    fn test_parse_unknown_region() {
+        let region: Region = "xx-newcity-1".parse().unwrap();
+        assert_eq!(region, Region::Other("xx-newcity-1".to_string()));
+        assert!(!region.is_known());
+        assert_eq!(region.realm(), None);
+        assert_eq!(region.to_string(), "xx-newcity-1");
+    }
+
+    #[test]
+    fn test_from_endpoint() {
+        for endpoint in [
+            "https://cell0.submit.email.ap-seoul-1.oci.oraclecloud.com",
+            "https://cell0.submit.email.ap-seoul-1.oci.oraclecloud.com/20220926/actions/submitEmail",
+            "ctrl.email.ap-seoul-1.oci.oraclecloud.com",
+            "https://EMAIL.AP-SEOUL-1.OCI.ORACLECLOUD.COM:443/",
+            "objectstorage.ap-seoul-1.oraclecloud.com",
+        ] {
+            assert_eq!(
+                Region::from_endpoint(endpoint).unwrap(),
+                Region::ApSeoul1,
+                "{}",
+                endpoint
+            );
+        }
+        assert_eq!(
+            Region::from_endpoint("identity.xx-newcity-1.oci.oraclecloud.com").unwrap(),
+            Region::Other("xx-newcity-1".to_string())
+        );
+
+        for endpoint in [
+            "",
+            "https://example.com",
+            "https://email.ap-seoul-1.example.com",
+            "oci.oraclecloud.com",
+            "https://email.seoul.oci.oraclecloud.com",
+            "ap-seoul-1",
+        ] {
+            assert!(
+                matches!(
+                    Region::from_endpoint(endpoint),
+                    Err(OciError::ConfigError(_))
+                ),
+                "{} should be rejected",
+                endpoint
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_malformed_region() {
+        for id in ["us-ashburn-01", "seoul", "ap-seoul", "ap seoul 1", ""] {
+            assert!(
+                matches!(id.parse::<Region>(), Err(OciError::ConfigError(_))),
+                "{} should be rejected",
+                id
+            );
+        }
+    }
+}
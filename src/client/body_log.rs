@@ -0,0 +1,338 @@
+//! Request and response body logging
+//!
+//! Logs the body of each request and response at debug level (target `oci_api::body`)
+//! via `tracing` when the `tracing` feature is enabled and logging is turned on with
+//! `OciClientBuilder::log_bodies`. Compiles to no-ops otherwise.
+//!
+//! Email addresses in bodies are masked by default (`u***@example.com`). The
+//! `authorization` header is logged with only the first characters of the signature.
+
+// Redaction is only used by the logging functions of the `tracing` feature
+#![cfg_attr(not(feature = "tracing"), allow(dead_code))]
+
+use crate::utils::mask_email;
+use std::borrow::Cow;
+
+/// Whether bodies can be logged (the `tracing` feature is enabled)
+pub(crate) const ENABLED: bool = cfg!(feature = "tracing");
+
+/// Log target of body logs
+const TARGET: &str = "oci_api::body";
+
+/// Number of signature characters kept in the logged `authorization` header
+const SIGNATURE_PREFIX_LEN: usize = 8;
+
+/// Body logging options (see `OciClientBuilder::log_bodies`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BodyLogging {
+    redact_emails: bool,
+}
+
+impl Default for BodyLogging {
+    /// Email addresses masked
+    fn default() -> Self {
+        Self {
+            redact_emails: true,
+        }
+    }
+}
+
+impl BodyLogging {
+    /// Log bodies with email addresses masked
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mask email addresses in logged bodies (default: true)
+    ///
+    /// Recipients are PII: only disable this on local development setups.
+    pub fn redact_emails(mut self, enabled: bool) -> Self {
+        self.redact_emails = enabled;
+        self
+    }
+
+    /// Body text as logged with these options
+    fn body<'a>(&self, body: &'a [u8]) -> Cow<'a, str> {
+        let text = String::from_utf8_lossy(body);
+        if !self.redact_emails {
+            return text;
+        }
+
+        match redact_emails(&text) {
+            Cow::Borrowed(_) => text,
+            Cow::Owned(redacted) => Cow::Owned(redacted),
+        }
+    }
+}
+
+/// Mask the email addresses in `text` (see `utils::mask_email`)
+pub(crate) fn redact_emails(text: &str) -> Cow<'_, str> {
+    let is_local = |c: char| c.is_alphanumeric() || "._%+-".contains(c);
+    let is_domain = |c: char| c.is_alphanumeric() || c == '.' || c == '-';
+
+    let mut redacted = String::new();
+    let mut copied = 0;
+    for (at, _) in text.match_indices('@') {
+        let local_start = text[copied..at]
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| is_local(*c))
+            .last()
+            .map(|(i, _)| copied + i);
+        let domain = text[at + 1..]
+            .split(|c: char| !is_domain(c))
+            .next()
+            .unwrap_or_default();
+
+        let Some(local_start) = local_start else {
+            continue;
+        };
+        if !domain.contains('.') {
+            continue;
+        }
+
+        let end = at + 1 + domain.len();
+        redacted.push_str(&text[copied..local_start]);
+        redacted.push_str(&mask_email(&text[local_start..end]));
+        copied = end;
+    }
+
+    if copied == 0 {
+        return Cow::Borrowed(text);
+    }
+    redacted.push_str(&text[copied..]);
+    Cow::Owned(redacted)
+}
+
+/// `authorization` header value with the signature cut to its first characters
+pub(crate) fn redact_authorization(value: &str) -> String {
+    const SIGNATURE: &str = "signature=\"";
+
+    let Some(start) = value.find(SIGNATURE).map(|i| i + SIGNATURE.len()) else {
+        return value.to_string();
+    };
+    let prefix: String = value[start..]
+        .chars()
+        .take_while(|c| *c != '"')
+        .take(SIGNATURE_PREFIX_LEN)
+        .collect();
+    format!("{}{}...\"", &value[..start], prefix)
+}
+
+/// Log a request about to be sent
+#[cfg(feature = "tracing")]
+pub(crate) fn log_request(options: &BodyLogging, service: &str, request: &reqwest::Request) {
+    if !tracing::enabled!(target: TARGET, tracing::Level::DEBUG) {
+        return;
+    }
+
+    let authorization = request
+        .headers()
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .map(redact_authorization)
+        .unwrap_or_default();
+    let body = match request.body() {
+        None => Cow::Borrowed(""),
+        Some(body) => match body.as_bytes() {
+            Some(bytes) => options.body(bytes),
+            None => Cow::Borrowed("<stream>"),
+        },
+    };
+
+    tracing::debug!(
+        target: TARGET,
+        service,
+        method = %request.method(),
+        url = %request.url(),
+        authorization = %authorization,
+        body = %body,
+        "request"
+    );
+}
+
+/// Log a request about to be sent (no-op without the `tracing` feature)
+#[cfg(not(feature = "tracing"))]
+#[inline(always)]
+pub(crate) fn log_request(_options: &BodyLogging, _service: &str, _request: &reqwest::Request) {}
+
+/// Log a response, buffering its body (the returned response replays it)
+#[cfg(feature = "tracing")]
+pub(crate) async fn log_response(
+    options: &BodyLogging,
+    service: &str,
+    response: reqwest::Response,
+) -> reqwest::Result<reqwest::Response> {
+    if !tracing::enabled!(target: TARGET, tracing::Level::DEBUG) {
+        return Ok(response);
+    }
+
+    let status = response.status();
+    let version = response.version();
+    let headers = response.headers().clone();
+    let bytes = response.bytes().await?;
+
+    tracing::debug!(
+        target: TARGET,
+        service,
+        status = status.as_u16(),
+        body = %options.body(&bytes),
+        "response"
+    );
+
+    let mut replay = http::Response::new(bytes);
+    *replay.status_mut() = status;
+    *replay.version_mut() = version;
+    *replay.headers_mut() = headers;
+    Ok(replay.into())
+}
+
+/// Log a response (no-op without the `tracing` feature)
+#[cfg(not(feature = "tracing"))]
+#[inline(always)]
+pub(crate) async fn log_response(
+    _options: &BodyLogging,
+    _service: &str,
+    response: reqwest::Response,
+) -> reqwest::Result<reqwest::Response> {
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_emails() {
+        assert_eq!(
+            redact_emails(
+                r#"{"to":[{"email":"user@example.com"}],"cc":"Jane.Doe+x@mail.example.co.kr"}"#
+            ),
+            r#"{"to":[{"email":"u***@example.com"}],"cc":"J***@mail.example.co.kr"}"#
+        );
+        assert_eq!(redact_emails("a@b.io, b@c.io"), "a***@b.io, b***@c.io");
+
+        // Not email addresses
+        for text in [
+            "",
+            "no addresses",
+            "@example.com",
+            "user@localhost",
+            "a @ b.com",
+        ] {
+            assert!(matches!(redact_emails(text), Cow::Borrowed(_)), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_redact_authorization() {
+        let value = r#"Signature version="1",keyId="ocid1.tenancy.oc1..t/ocid1.user.oc1..u/aa:bb",algorithm="rsa-sha256",headers="date (request-target) host",signature="abcdefghijklmnopqrstuvwxyz==""#;
+        assert_eq!(
+            redact_authorization(value),
+            r#"Signature version="1",keyId="ocid1.tenancy.oc1..t/ocid1.user.oc1..u/aa:bb",algorithm="rsa-sha256",headers="date (request-target) host",signature="abcdefgh...""#
+        );
+        assert_eq!(redact_authorization("Bearer x"), "Bearer x");
+    }
+
+    #[test]
+    fn test_body_options() {
+        let body = br#"{"email":"user@example.com"}"#;
+        assert_eq!(
+            BodyLogging::default().body(body),
+            r#"{"email":"u***@example.com"}"#
+        );
+        assert_eq!(
+            BodyLogging::new().redact_emails(false).body(body),
+            r#"{"email":"user@example.com"}"#
+        );
+    }
+
+    #[cfg(feature = "tracing")]
+    mod tracing_tests {
+        use super::*;
+        use crate::client::OciClient;
+        use crate::client::test_utils::test_config;
+        use reqwest::Method;
+        use std::fmt::{Debug, Write};
+        use std::sync::{Arc, Mutex};
+        use tracing::field::Field;
+        use tracing::{Event, Metadata, span};
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        /// Subscriber recording the fields of each event
+        struct Capture(Arc<Mutex<Vec<String>>>);
+
+        impl tracing::Subscriber for Capture {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+                span::Id::from_u64(1)
+            }
+
+            fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+            fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+            fn event(&self, event: &Event<'_>) {
+                let mut line = String::new();
+                event.record(&mut |field: &Field, value: &dyn Debug| {
+                    let _ = write!(line, "{}={:?} ", field.name(), value);
+                });
+                self.0.lock().unwrap().push(line);
+            }
+
+            fn enter(&self, _: &span::Id) {}
+
+            fn exit(&self, _: &span::Id) {}
+        }
+
+        #[tokio::test]
+        async fn test_logged_bodies_are_redacted() {
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(serde_json::json!({"sender": "noreply@example.com"})),
+                )
+                .mount(&server)
+                .await;
+
+            let logs = Arc::new(Mutex::new(Vec::new()));
+            let _guard = tracing::subscriber::set_default(Capture(logs.clone()));
+
+            let client = OciClient::builder(&test_config())
+                .log_bodies(BodyLogging::default())
+                .build()
+                .unwrap();
+            let response = client
+                .execute(
+                    "email",
+                    Method::POST,
+                    &server.uri(),
+                    "/send",
+                    Some(r#"{"to":"user@example.com"}"#.to_string()),
+                )
+                .await
+                .unwrap();
+
+            // The logged response body can still be read
+            let body: serde_json::Value = response.json().await.unwrap();
+            assert_eq!(body["sender"], "noreply@example.com");
+
+            let logs = logs.lock().unwrap().join("\n");
+            assert!(logs.contains(r#"body={"to":"u***@example.com"}"#), "{logs}");
+            assert!(
+                logs.contains(r#"body={"sender":"n***@example.com"}"#),
+                "{logs}"
+            );
+            assert!(!logs.contains("user@example.com"), "{logs}");
+            assert!(!logs.contains("noreply@example.com"), "{logs}");
+
+            let signature = logs.split("signature=\"").nth(1).unwrap();
+            assert_eq!(signature.find('"'), Some(SIGNATURE_PREFIX_LEN + 3));
+        }
+    }
+}
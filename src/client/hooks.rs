@@ -0,0 +1,97 @@
+//! Request hooks
+//!
+//! Lightweight callbacks invoked around each signed request, for logging or
+//! recording per-request info without the `metrics` feature.
+
+use reqwest::{Method, StatusCode};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Request hook
+pub(crate) type Hook = Arc<dyn Fn(&RequestInfo<'_>) + Send + Sync>;
+
+/// Information about a request passed to hooks
+#[derive(Debug, Clone)]
+pub struct RequestInfo<'a> {
+    /// Service name (e.g., "email")
+    pub service: &'a str,
+
+    /// HTTP method
+    pub method: &'a Method,
+
+    /// Host (with port, if any)
+    pub host: &'a str,
+
+    /// Request path including query string
+    pub path: &'a str,
+
+    /// Client-generated `opc-request-id` shared by all attempts of one call
+    pub correlation_id: &'a str,
+
+    /// Attempt number of the call (1 for the first attempt, incremented per retry)
+    pub attempt: u32,
+
+    /// Response status (`on_response` only; `None` if no response was received)
+    pub status: Option<StatusCode>,
+
+    /// Time from sending the request to receiving the response headers (`on_response` only)
+    pub elapsed: Option<Duration>,
+
+    /// `opc-request-id` response header of this attempt (`on_response` only)
+    pub opc_request_id: Option<&'a str>,
+}
+
+impl<'a> RequestInfo<'a> {
+    /// Info for a request about to be sent
+    pub(crate) fn new(
+        service: &'a str,
+        method: &'a Method,
+        host: &'a str,
+        path: &'a str,
+        correlation_id: &'a str,
+        attempt: u32,
+    ) -> Self {
+        Self {
+            service,
+            method,
+            host,
+            path,
+            correlation_id,
+            attempt,
+            status: None,
+            elapsed: None,
+            opc_request_id: None,
+        }
+    }
+}
+
+/// Invoke hooks, ignoring panics so that a faulty hook can't break the request
+pub(crate) fn invoke(hooks: &[Hook], info: &RequestInfo<'_>) {
+    for hook in hooks {
+        let _ = catch_unwind(AssertUnwindSafe(|| hook(info)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_invoke_ignores_panics() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+
+        let hooks: Vec<Hook> = vec![
+            Arc::new(|_| panic!("faulty hook")),
+            Arc::new(move |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }),
+        ];
+        let info = RequestInfo::new("email", &Method::GET, "example.com", "/", "ID", 1);
+
+        invoke(&hooks, &info);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
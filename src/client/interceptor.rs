@@ -0,0 +1,130 @@
+//! Request interceptors
+//!
+//! Unlike hooks, which only observe requests, interceptors can modify a request after it
+//! is signed and before it is sent, e.g. to add a tracing or tenant correlation header.
+
+use reqwest::header::HeaderMap;
+use reqwest::{Method, Request, Url};
+use std::sync::Arc;
+
+/// Headers covered by the request signature (plus `authorization`, which carries it)
+///
+/// Interceptors can't change these: their original values are restored after interceptors
+/// run. `(request-target)` (method, path and query) is signed too.
+pub const SIGNED_HEADERS: &[&str] = &[
+    "date",
+    "host",
+    "content-length",
+    "content-type",
+    "x-content-sha256",
+    "authorization",
+];
+
+/// Request modifier invoked on every signed request before it is sent
+///
+/// Interceptors run in registration order (see `OciClientBuilder::interceptor`), once per
+/// attempt. Changes to `SIGNED_HEADERS` are discarded. The URL path and query are signed,
+/// so changing them makes OCI reject the request; changing the scheme, host or port (e.g.
+/// to go through a proxy) is fine, since the `host` header is kept.
+///
+/// # Example
+/// ```
+/// # use oci_api::client::{Interceptor, RequestParts};
+/// struct TenantHeader(String);
+///
+/// impl Interceptor for TenantHeader {
+///     fn before_send(&self, req: &mut RequestParts) {
+///         if let Ok(value) = self.0.parse() {
+///             req.headers.insert("x-tenant-id", value);
+///         }
+///     }
+/// }
+/// ```
+pub trait Interceptor: Send + Sync {
+    /// Modify the request about to be sent
+    fn before_send(&self, req: &mut RequestParts);
+}
+
+/// Request passed to interceptors
+#[derive(Debug, Clone)]
+pub struct RequestParts {
+    method: Method,
+
+    /// Request URL
+    pub url: Url,
+
+    /// Request headers, including the signed ones (changes to those are discarded)
+    pub headers: HeaderMap,
+}
+
+impl RequestParts {
+    /// HTTP method (signed, so read-only)
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+}
+
+/// Registered interceptor
+pub(crate) type SharedInterceptor = Arc<dyn Interceptor>;
+
+/// Run `interceptors` on `request`, keeping the signed headers as they were
+pub(crate) fn apply(interceptors: &[SharedInterceptor], request: &mut Request) {
+    if interceptors.is_empty() {
+        return;
+    }
+
+    let mut parts = RequestParts {
+        method: request.method().clone(),
+        url: request.url().clone(),
+        headers: request.headers().clone(),
+    };
+    for interceptor in interceptors {
+        interceptor.before_send(&mut parts);
+    }
+
+    let original = std::mem::replace(request.headers_mut(), parts.headers);
+    let headers = request.headers_mut();
+    for name in SIGNED_HEADERS {
+        headers.remove(*name);
+        for value in original.get_all(*name) {
+            headers.append(*name, value.clone());
+        }
+    }
+    *request.url_mut() = parts.url;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Rewriter;
+
+    impl Interceptor for Rewriter {
+        fn before_send(&self, req: &mut RequestParts) {
+            req.headers.insert("x-custom", "1".parse().unwrap());
+            req.headers.insert("date", "tampered".parse().unwrap());
+            req.headers.remove("authorization");
+            req.url.set_port(Some(9999)).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_signed_headers_are_restored() {
+        let client = reqwest::Client::new();
+        let mut request = client
+            .get("http://127.0.0.1:8080/path?q=1")
+            .header("date", "Thu, 01 Jan 2024 00:00:00 GMT")
+            .header("authorization", "Signature ...")
+            .build()
+            .unwrap();
+
+        apply(&[Arc::new(Rewriter)], &mut request);
+
+        let headers = request.headers();
+        assert_eq!(headers["x-custom"], "1");
+        assert_eq!(headers["date"], "Thu, 01 Jan 2024 00:00:00 GMT");
+        assert_eq!(headers["authorization"], "Signature ...");
+        assert!(!headers.contains_key("content-length"));
+        assert_eq!(request.url().as_str(), "http://127.0.0.1:9999/path?q=1");
+    }
+}
@@ -0,0 +1,55 @@
+//! Request metrics
+//!
+//! Emits request counters and latency histograms via the `metrics` crate facade
+//! when the `metrics` feature is enabled. Compiles to no-ops otherwise.
+//!
+//! # Metrics
+//! - `oci_api_requests_total{service, status}`: requests sent (status is the HTTP status code, or `error`)
+//! - `oci_api_errors_total{service, status}`: requests that failed or returned a non-success status
+//! - `oci_api_request_duration_seconds{service}`: request latency histogram
+//! - `oci_api_retries_total{service}`: attempts retried after a retryable error
+
+use reqwest::StatusCode;
+use std::time::Duration;
+
+/// Record a finished request
+///
+/// `status` is `None` when no response was received (e.g. connection error).
+#[cfg(feature = "metrics")]
+pub(crate) fn record_request(service: &'static str, status: Option<StatusCode>, elapsed: Duration) {
+    let is_success = status.is_some_and(|s| s.is_success());
+    let status = status
+        .map(|s| s.as_u16().to_string())
+        .unwrap_or_else(|| "error".to_string());
+
+    metrics::counter!("oci_api_requests_total", "service" => service, "status" => status.clone())
+        .increment(1);
+    metrics::histogram!("oci_api_request_duration_seconds", "service" => service)
+        .record(elapsed.as_secs_f64());
+
+    if !is_success {
+        metrics::counter!("oci_api_errors_total", "service" => service, "status" => status)
+            .increment(1);
+    }
+}
+
+/// Record a retry (an attempt after the first)
+#[cfg(feature = "metrics")]
+pub(crate) fn record_retry(service: &'static str) {
+    metrics::counter!("oci_api_retries_total", "service" => service).increment(1);
+}
+
+/// Record a finished request (no-op without the `metrics` feature)
+#[cfg(not(feature = "metrics"))]
+#[inline(always)]
+pub(crate) fn record_request(
+    _service: &'static str,
+    _status: Option<StatusCode>,
+    _elapsed: Duration,
+) {
+}
+
+/// Record a retry (no-op without the `metrics` feature)
+#[cfg(not(feature = "metrics"))]
+#[inline(always)]
+pub(crate) fn record_retry(_service: &'static str) {}
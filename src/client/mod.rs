@@ -1,6 +1,22 @@
 //! OCI client module
 
+mod body_log;
+mod hooks;
 mod http;
+mod in_flight;
+mod interceptor;
+mod metrics;
+mod paginator;
 pub(crate) mod signer;
+mod stream;
+#[cfg(test)]
+pub(crate) mod test_utils;
 
-pub use http::OciClient;
+pub use body_log::BodyLogging;
+pub use hooks::RequestInfo;
+pub use http::{DEFAULT_CLIENT_INFO, DEFAULT_USER_AGENT, OciClient, OciClientBuilder};
+pub(crate) use http::{base_url, parse_json_body, response_json, split_endpoint};
+pub use interceptor::{Interceptor, RequestParts, SIGNED_HEADERS};
+pub use paginator::{Page, Paginator};
+pub use signer::{OciSigner, SigningKeyKind};
+pub use stream::StreamBody;
@@ -0,0 +1,247 @@
+//! Pagination over `opc-next-page` cursors
+//!
+//! OCI list APIs return one page per request and the cursor of the next page in the
+//! `opc-next-page` response header, to be passed back as the `page` query parameter.
+//! `Paginator` drives that loop for any list endpoint and yields the items one by one.
+
+use crate::error::Result;
+use futures_core::Stream;
+use reqwest::Response;
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+/// One page of a list API
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page<T> {
+    /// Items of the page
+    pub items: Vec<T>,
+
+    /// Cursor of the next page (`opc-next-page`), `None` on the last page
+    pub next_page: Option<String>,
+}
+
+impl<T: DeserializeOwned> Page<T> {
+    /// Read a page from a list API response: a JSON array body and the `opc-next-page` header
+    pub async fn from_response(response: Response) -> Result<Self> {
+        let next_page = response
+            .headers()
+            .get("opc-next-page")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let items = super::response_json(response).await?;
+        Ok(Self { items, next_page })
+    }
+}
+
+type PageFuture<'a, T> = Pin<Box<dyn Future<Output = Result<Page<T>>> + Send + 'a>>;
+type FetchPage<'a, T> = Box<dyn FnMut(Option<String>) -> PageFuture<'a, T> + Send + 'a>;
+
+/// Stream of the items of all pages of a list API
+///
+/// Pages are fetched lazily, one at a time, as items are consumed. The stream ends after
+/// the last page, or after yielding the error of a failed page.
+///
+/// # Example
+/// ```no_run
+/// # use oci_api::client::{OciClient, Page, Paginator};
+/// # async fn example(client: &OciClient, endpoint: &str) -> oci_api::Result<()> {
+/// let paginator: Paginator<'_, serde_json::Value> = Paginator::new(|page| async move {
+///     let path = match page {
+///         Some(page) => format!("/20160918/users?compartmentId=ocid1.tenancy.oc1..x&page={}", page),
+///         None => "/20160918/users?compartmentId=ocid1.tenancy.oc1..x".to_string(),
+///     };
+///     let response = client
+///         .execute_with_timeout("identity", reqwest::Method::GET, endpoint, &path, None, None)
+///         .await?;
+///     Page::from_response(response).await
+/// });
+/// let users = paginator.try_collect().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Paginator<'a, T> {
+    fetch: FetchPage<'a, T>,
+    pending: Option<PageFuture<'a, T>>,
+    buffer: VecDeque<T>,
+    cursor: Option<String>,
+    done: bool,
+}
+
+impl<'a, T> Paginator<'a, T> {
+    /// Paginate with `fetch`, called with the cursor of each page (`None` for the first)
+    pub fn new<F, Fut>(mut fetch: F) -> Self
+    where
+        F: FnMut(Option<String>) -> Fut + Send + 'a,
+        Fut: Future<Output = Result<Page<T>>> + Send + 'a,
+    {
+        Self {
+            fetch: Box::new(move |cursor| Box::pin(fetch(cursor))),
+            pending: None,
+            buffer: VecDeque::new(),
+            cursor: None,
+            done: false,
+        }
+    }
+
+    /// Next item, fetching the next page when needed (`None` after the last item)
+    pub async fn next(&mut self) -> Option<Result<T>> {
+        std::future::poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+
+    /// Fetch all remaining pages and collect their items, stopping at the first error
+    pub async fn try_collect(mut self) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+        while let Some(item) = self.next().await {
+            items.push(item?);
+        }
+        Ok(items)
+    }
+}
+
+// Items are only buffered and moved out, never pinned, so the paginator can move freely
+impl<T> Unpin for Paginator<'_, T> {}
+
+impl<T> Stream for Paginator<'_, T> {
+    type Item = Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(item) = this.buffer.pop_front() {
+                return Poll::Ready(Some(Ok(item)));
+            }
+
+            if let Some(pending) = &mut this.pending {
+                let result = ready!(pending.as_mut().poll(cx));
+                this.pending = None;
+                match result {
+                    Ok(page) => {
+                        this.buffer.extend(page.items);
+                        this.cursor = page.next_page;
+                        this.done = this.cursor.is_none();
+                    }
+                    Err(e) => {
+                        this.done = true;
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                }
+                continue;
+            }
+
+            if this.done {
+                return Poll::Ready(None);
+            }
+            this.pending = Some((this.fetch)(this.cursor.take()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_utils::test_client;
+    use crate::error::OciError;
+    use reqwest::Method;
+    use std::sync::Mutex;
+    use wiremock::matchers::{method, query_param, query_param_is_missing};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Mock server with three pages: `[1, 2]`, `[3]` and `[4, 5]`
+    async fn three_page_server() -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param_is_missing("page"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("opc-next-page", "p2")
+                    .set_body_json([1, 2]),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(query_param("page", "p2"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("opc-next-page", "p3")
+                    .set_body_json([3]),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(query_param("page", "p3"))
+            .respond_with(ResponseTemplate::new(200).set_body_json([4, 5]))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    fn paginator<'a>(
+        client: &'a crate::client::OciClient,
+        endpoint: &'a str,
+    ) -> Paginator<'a, u32> {
+        Paginator::new(move |page| async move {
+            let path = match page {
+                Some(page) => format!("/items?page={}", page),
+                None => "/items".to_string(),
+            };
+            let response = client
+                .execute("test", Method::GET, endpoint, &path, None)
+                .await?;
+            Page::from_response(response).await
+        })
+    }
+
+    #[tokio::test]
+    async fn test_paginator_three_pages() {
+        let server = three_page_server().await;
+        let client = test_client();
+        let endpoint = server.uri();
+
+        let items = paginator(&client, &endpoint).try_collect().await.unwrap();
+        assert_eq!(items, [1, 2, 3, 4, 5]);
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_paginator_fetches_lazily() {
+        let server = three_page_server().await;
+        let client = test_client();
+        let endpoint = server.uri();
+
+        let mut paginator = paginator(&client, &endpoint);
+        assert_eq!(paginator.next().await.unwrap().unwrap(), 1);
+        assert_eq!(paginator.next().await.unwrap().unwrap(), 2);
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+        assert_eq!(paginator.next().await.unwrap().unwrap(), 3);
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_paginator_stops_after_error() {
+        let cursors = Mutex::new(Vec::new());
+        let mut paginator: Paginator<'_, u32> = Paginator::new(|page| {
+            cursors.lock().unwrap().push(page.clone());
+            async move {
+                match page {
+                    None => Ok(Page {
+                        items: vec![1],
+                        next_page: Some("p2".to_string()),
+                    }),
+                    Some(_) => Err(OciError::Other("page failed".to_string())),
+                }
+            }
+        });
+
+        assert_eq!(paginator.next().await.unwrap().unwrap(), 1);
+        assert!(matches!(
+            paginator.next().await,
+            Some(Err(OciError::Other(_)))
+        ));
+        assert!(paginator.next().await.is_none());
+        assert_eq!(*cursors.lock().unwrap(), [None, Some("p2".to_string())]);
+    }
+}
@@ -0,0 +1,89 @@
+//! Streaming request bodies
+//!
+//! OCI signs the `x-content-sha256` digest of the body, so the digest must be known
+//! before the first byte is sent. A streamed body therefore takes two passes:
+//! one to compute the length and digest, and one to send it.
+
+use crate::error::Result;
+use crate::utils::body_sha256_base64;
+use std::path::Path;
+use tokio::io::AsyncRead;
+use tokio_util::io::ReaderStream;
+
+/// Request body sent as a stream (see `OciClient::execute_stream`)
+pub struct StreamBody {
+    pub(crate) body: reqwest::Body,
+    pub(crate) content_length: u64,
+    pub(crate) sha256: String,
+    pub(crate) content_type: String,
+}
+
+impl StreamBody {
+    /// Body from an in-memory buffer (digest computed here)
+    pub fn from_bytes(bytes: impl Into<Vec<u8>>, content_type: impl Into<String>) -> Self {
+        let bytes = bytes.into();
+        Self {
+            content_length: bytes.len() as u64,
+            sha256: body_sha256_base64(&bytes),
+            body: reqwest::Body::from(bytes),
+            content_type: content_type.into(),
+        }
+    }
+
+    /// Body streamed from `reader`, with a pre-computed length and digest
+    ///
+    /// `content_length` and `body_sha256` must describe exactly the bytes `reader` yields
+    /// (see `utils::body_sha256_base64_reader` for the first pass); otherwise OCI rejects
+    /// the signature.
+    ///
+    /// # Arguments
+    /// * `reader` - Body source
+    /// * `content_length` - Body length in bytes
+    /// * `body_sha256` - Base64 SHA-256 digest of the body
+    /// * `content_type` - Body content type
+    pub fn from_reader<R>(
+        reader: R,
+        content_length: u64,
+        body_sha256: impl Into<String>,
+        content_type: impl Into<String>,
+    ) -> Self
+    where
+        R: AsyncRead + Send + Sync + 'static,
+    {
+        Self {
+            body: reqwest::Body::wrap_stream(ReaderStream::new(reader)),
+            content_length,
+            sha256: body_sha256.into(),
+            content_type: content_type.into(),
+        }
+    }
+
+    /// Body streamed from a file, read twice: once for the digest, once to send
+    ///
+    /// The file must not change between the two passes.
+    pub async fn from_file(
+        path: impl AsRef<Path>,
+        content_type: impl Into<String>,
+    ) -> Result<Self> {
+        let path = path.as_ref();
+        let (content_length, sha256) =
+            crate::utils::body_sha256_base64_reader(tokio::fs::File::open(path).await?).await?;
+        let file = tokio::fs::File::open(path).await?;
+        Ok(Self::from_reader(
+            file,
+            content_length,
+            sha256,
+            content_type,
+        ))
+    }
+
+    /// Body length in bytes
+    pub fn content_length(&self) -> u64 {
+        self.content_length
+    }
+
+    /// Base64 SHA-256 digest of the body
+    pub fn sha256(&self) -> &str {
+        &self.sha256
+    }
+}
@@ -0,0 +1,37 @@
+//! Shared helpers for unit tests
+
+use crate::auth::OciConfig;
+use crate::client::OciClient;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+
+/// Valid RSA private key (PKCS#8 PEM) for tests
+pub(crate) const TEST_KEY: &str = include_str!("../../tests/fixtures/test_key.pem");
+
+/// Second valid RSA private key (PKCS#8 PEM), different from `TEST_KEY`
+pub(crate) const TEST_KEY_2: &str = include_str!("../../tests/fixtures/test_key_2.pem");
+
+/// Configuration using `TEST_KEY`
+pub(crate) fn test_config() -> OciConfig {
+    OciConfig {
+        user_id: "ocid1.user.oc1..test".to_string(),
+        tenancy_id: "ocid1.tenancy.oc1..test".to_string(),
+        region: "ap-seoul-1".to_string(),
+        fingerprint: "aa:bb:cc:dd:ee:ff".to_string(),
+        private_key: TEST_KEY.to_string(),
+        compartment_id: None,
+    }
+}
+
+/// Client using `test_config()`
+pub(crate) fn test_client() -> OciClient {
+    OciClient::new(&test_config()).expect("Failed to create test client")
+}
+
+/// Unsigned JWT security token with `claims` (only the payload is read)
+pub(crate) fn test_token(claims: serde_json::Value) -> String {
+    format!(
+        "eyJhbGciOiJSUzI1NiJ9.{}.c2lnbmF0dXJl",
+        URL_SAFE_NO_PAD.encode(claims.to_string())
+    )
+}
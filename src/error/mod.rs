@@ -2,9 +2,15 @@
 //!
 //! This module defines all possible errors that can occur when using OCI API.
 
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
 use thiserror::Error;
 
 /// OCI API error type
+///
+/// `Send + Sync + 'static`, so it can be returned from spawned tasks and converted
+/// into `anyhow::Error` or `Box<dyn Error + Send + Sync>`.
 #[derive(Debug, Error)]
 pub enum OciError {
     /// Configuration file related error
@@ -23,6 +29,18 @@ pub enum OciError {
     #[error("Private key error: {0}")]
     KeyError(String),
 
+    /// Private key that can't be parsed as a PKCS#8 RSA key
+    #[error("Private key parse error: {0}")]
+    KeyParseError(#[from] rsa::pkcs8::Error),
+
+    /// Request signing error
+    #[error("Signing error: {0}")]
+    SigningError(#[from] rsa::signature::Error),
+
+    /// Malformed base64 input
+    #[error("Base64 decode error: {0}")]
+    Base64Error(#[from] base64::DecodeError),
+
     /// HTTP request/response error
     #[error("HTTP error: {0}")]
     HttpError(#[from] reqwest::Error),
@@ -31,6 +49,15 @@ pub enum OciError {
     #[error("JSON error: {0}")]
     JsonError(#[from] serde_json::Error),
 
+    /// Response body that isn't the expected JSON (e.g. an HTML page from a proxy)
+    #[error("Invalid JSON response: {source}. Body: {body_snippet}")]
+    JsonResponseError {
+        /// Parse error
+        source: serde_json::Error,
+        /// Start of the response body, with email addresses masked
+        body_snippet: String,
+    },
+
     /// API response error (error returned by OCI API)
     #[error("API error (code: {code}): {message}")]
     ApiError {
@@ -38,8 +65,70 @@ pub enum OciError {
         code: String,
         /// Error message
         message: String,
+        /// Structured error body (if the response body could be parsed)
+        details: Option<Box<OciApiErrorBody>>,
+        /// Request ids of all attempts of the call (if the request was sent by `OciClient`)
+        trace: Option<Box<RequestTrace>>,
+        /// Delay requested by the `Retry-After` response header, if any
+        /// (see `utils::retry::parse_retry_after`)
+        retry_after: Option<Duration>,
+    },
+
+    /// Precondition failed (412): the `if-match` ETag no longer matches the resource
+    ///
+    /// The resource was changed concurrently; fetch it again to get the current ETag.
+    #[error("Precondition failed (ETag mismatch): {message}")]
+    PreconditionFailed {
+        /// Error message
+        message: String,
+        /// Structured error body (if the response body could be parsed)
+        details: Option<Box<OciApiErrorBody>>,
+        /// Request ids of all attempts of the call (if the request was sent by `OciClient`)
+        trace: Option<Box<RequestTrace>>,
+    },
+
+    /// Not authenticated (401): OCI couldn't verify the request signature
+    ///
+    /// Usually a wrong user or tenancy OCID, a fingerprint that doesn't match the private
+    /// key, a key not uploaded to the user, or a system clock off by more than 5 minutes.
+    #[error(
+        "Not authenticated (401): {message}. Check the user and tenancy OCIDs, that the \
+         fingerprint matches the private key uploaded to the user, and that the system clock \
+         is accurate"
+    )]
+    Unauthorized {
+        /// Error message
+        message: String,
+        /// Structured error body (if the response body could be parsed)
+        details: Option<Box<OciApiErrorBody>>,
+        /// Request ids of all attempts of the call (if the request was sent by `OciClient`)
+        trace: Option<Box<RequestTrace>>,
+    },
+
+    /// Not authorized (403): the request was authenticated, but no IAM policy allows it
+    #[error(
+        "Not authorized (403) to {action} in compartment {}: {message}. The user's \
+         groups are likely missing an IAM policy allowing this in the compartment",
+        crate::utils::mask_ocid(.compartment)
+    )]
+    Forbidden {
+        /// Error message
+        message: String,
+        /// Denied action (e.g., `POST /20220926/actions/submitEmail`)
+        action: String,
+        /// Compartment of the request (the `compartmentId` query parameter, else the
+        /// client's default compartment), masked in the message (see `utils::mask_ocid`)
+        compartment: String,
+        /// Structured error body (if the response body could be parsed)
+        details: Option<Box<OciApiErrorBody>>,
+        /// Request ids of all attempts of the call (if the request was sent by `OciClient`)
+        trace: Option<Box<RequestTrace>>,
     },
 
+    /// Region that can't be mapped to a realm and service endpoints (e.g., malformed id)
+    #[error("Unsupported region: {0}")]
+    UnsupportedRegion(String),
+
     /// I/O error
     #[error("I/O error: {0}")]
     IoError(#[from] std::io::Error),
@@ -56,10 +145,341 @@ pub enum OciError {
 /// Result type alias
 pub type Result<T> = std::result::Result<T, OciError>;
 
+/// Error body returned by OCI APIs
+///
+/// All fields are optional because the body shape differs slightly between services.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OciApiErrorBody {
+    /// Service error code (e.g., `NotAuthorizedOrNotFound`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+
+    /// Error message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+
+    /// Original (untranslated) error message
+    #[serde(rename = "originalMessage", skip_serializing_if = "Option::is_none")]
+    pub original_message: Option<String>,
+
+    /// Template of the original error message
+    #[serde(
+        rename = "originalMessageTemplate",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub original_message_template: Option<String>,
+
+    /// Arguments for the message template
+    #[serde(rename = "messageArguments", skip_serializing_if = "Option::is_none")]
+    pub message_arguments: Option<HashMap<String, serde_json::Value>>,
+
+    /// Request ID echoed in the body (useful for support tickets)
+    #[serde(rename = "opc-request-id", skip_serializing_if = "Option::is_none")]
+    pub opc_request_id: Option<String>,
+}
+
+/// Request ids of one logical call across its attempts (retries and fallback-key retry)
+///
+/// Each attempt gets its own `opc-request-id` from OCI. All attempts send the same
+/// client-generated `correlation_id` as the `opc-request-id` request header, so they can be
+/// correlated in logs (see `RequestInfo`) and in support requests.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestTrace {
+    /// Client-generated request id shared by all attempts
+    pub correlation_id: String,
+
+    /// `opc-request-id` response header of each attempt, in order
+    pub opc_request_ids: Vec<String>,
+}
+
+impl OciError {
+    /// Build `ApiError` from a non-success HTTP response
+    ///
+    /// Uses the structured error body when it can be parsed, otherwise the raw body text.
+    pub(crate) async fn from_response(response: reqwest::Response) -> Self {
+        let status = response.status();
+        let delay = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(crate::utils::retry::parse_retry_after);
+        let body = match response.text().await {
+            Ok(body) => body,
+            Err(e) => return OciError::HttpError(e),
+        };
+
+        let mut error = Self::from_status_and_body(status, &body);
+        if let OciError::ApiError { retry_after, .. } = &mut error {
+            *retry_after = delay;
+        }
+        error
+    }
+
+    /// Build `ApiError` (or `Unauthorized`, `Forbidden` and `PreconditionFailed` for 401, 403
+    /// and 412) from HTTP status and response body text
+    ///
+    /// `Forbidden` gets a placeholder action and compartment (see `with_request_context`).
+    pub(crate) fn from_status_and_body(status: reqwest::StatusCode, body: &str) -> Self {
+        let details = serde_json::from_str::<OciApiErrorBody>(body).ok();
+        let message = details
+            .as_ref()
+            .and_then(|d| d.message.clone())
+            .unwrap_or_else(|| body.to_string());
+
+        match status {
+            reqwest::StatusCode::UNAUTHORIZED => {
+                return OciError::Unauthorized {
+                    message,
+                    details: details.map(Box::new),
+                    trace: None,
+                };
+            }
+            reqwest::StatusCode::FORBIDDEN => {
+                return OciError::Forbidden {
+                    message,
+                    action: "perform this request".to_string(),
+                    compartment: "unknown".to_string(),
+                    details: details.map(Box::new),
+                    trace: None,
+                };
+            }
+            reqwest::StatusCode::PRECONDITION_FAILED => {
+                return OciError::PreconditionFailed {
+                    message,
+                    details: details.map(Box::new),
+                    trace: None,
+                };
+            }
+            _ => {}
+        }
+
+        OciError::ApiError {
+            code: status.to_string(),
+            message,
+            details: details.map(Box::new),
+            trace: None,
+            retry_after: None,
+        }
+    }
+
+    /// Delay requested by the `Retry-After` header of an `ApiError` response (e.g. 429 or 503)
+    ///
+    /// `retry_with_backoff` waits at least this long before retrying, and doesn't retry if
+    /// it is longer than `max_delay`.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            OciError::ApiError { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// Whether the error is worth retrying: throttling (429) or a transient server error
+    /// (500, 502, 503, 504)
+    ///
+    /// Connection and timeout errors are not retryable, since the request may have been
+    /// performed.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            OciError::ApiError { code, .. } => ["429", "500", "502", "503", "504"]
+                .iter()
+                .any(|status| code.starts_with(status)),
+            _ => false,
+        }
+    }
+
+    /// HTTP status to answer with when this error ends a request of a service wrapping OCI
+    ///
+    /// * 400: invalid input (`ConfigError`, e.g. email validation, and `UnsupportedRegion`)
+    /// * 401 / 403: `AuthError` and `Unauthorized` / `Forbidden`
+    /// * 412: `PreconditionFailed`
+    /// * OCI client errors (4xx `ApiError`, e.g. 404 not found or 429 throttling) as-is
+    /// * 502: OCI server errors (5xx `ApiError`), failed requests (`HttpError`) and
+    ///   unparseable responses (`JsonResponseError`)
+    /// * 504: timeouts (`HttpError` timeouts and 504 `ApiError`)
+    /// * 500: local errors (environment, key, I/O, JSON and other errors)
+    ///
+    /// # Example
+    /// ```
+    /// # use oci_api::OciError;
+    /// let error = OciError::ConfigError("Too many recipients".to_string());
+    /// assert_eq!(error.http_status_hint(), 400);
+    /// ```
+    pub fn http_status_hint(&self) -> u16 {
+        match self {
+            OciError::ConfigError(_) | OciError::UnsupportedRegion(_) => 400,
+            OciError::AuthError(_) | OciError::Unauthorized { .. } => 401,
+            OciError::Forbidden { .. } => 403,
+            OciError::PreconditionFailed { .. } => 412,
+            OciError::ApiError { code, .. } => match api_status(code) {
+                Some(status @ 400..=499) => status,
+                Some(504) => 504,
+                _ => 502,
+            },
+            OciError::HttpError(e) if e.is_timeout() => 504,
+            OciError::HttpError(_) | OciError::JsonResponseError { .. } => 502,
+            OciError::EnvError(_)
+            | OciError::KeyError(_)
+            | OciError::KeyParseError(_)
+            | OciError::SigningError(_)
+            | OciError::Base64Error(_)
+            | OciError::JsonError(_)
+            | OciError::IoError(_)
+            | OciError::IniError(_)
+            | OciError::Other(_) => 500,
+        }
+    }
+
+    /// Whether the error is a 401: the request signature or credentials were rejected
+    pub fn is_unauthorized(&self) -> bool {
+        matches!(self, OciError::Unauthorized { .. })
+    }
+
+    /// Whether the error is a 403: authenticated, but not allowed by IAM policies
+    pub fn is_forbidden(&self) -> bool {
+        matches!(self, OciError::Forbidden { .. })
+    }
+
+    /// Name the denied action and compartment in `Forbidden` (other errors are returned as-is)
+    pub(crate) fn with_request_context(
+        mut self,
+        method: &reqwest::Method,
+        path: &str,
+        default_compartment: &str,
+    ) -> Self {
+        if let OciError::Forbidden {
+            action,
+            compartment,
+            ..
+        } = &mut self
+        {
+            let (path, query) = path.split_once('?').unwrap_or((path, ""));
+            *action = format!("{} {}", method, path);
+            *compartment = query
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("compartmentId="))
+                .unwrap_or(default_compartment)
+                .to_string();
+        }
+        self
+    }
+
+    /// Attach the request trace of the call to `ApiError`, `Unauthorized`, `Forbidden` and
+    /// `PreconditionFailed` (other errors are returned as-is)
+    pub(crate) fn with_trace(mut self, request_trace: RequestTrace) -> Self {
+        if let OciError::ApiError { trace, .. }
+        | OciError::Unauthorized { trace, .. }
+        | OciError::Forbidden { trace, .. }
+        | OciError::PreconditionFailed { trace, .. } = &mut self
+        {
+            *trace = Some(Box::new(request_trace));
+        }
+        self
+    }
+}
+
+/// HTTP status of an `ApiError` code (e.g. `404 Not Found`)
+fn api_status(code: &str) -> Option<u16> {
+    code.split_whitespace().next()?.parse().ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// `OciError` must stay usable with `tokio::spawn`, `anyhow` and `Box<dyn Error + Send + Sync>`
+    #[test]
+    fn test_error_is_send_sync_static() {
+        fn assert<T: std::error::Error + Send + Sync + 'static>() {}
+        assert::<OciError>();
+
+        let boxed: Box<dyn std::error::Error + Send + Sync> =
+            Box::new(OciError::Other("test".to_string()));
+        assert_eq!(boxed.to_string(), "Other error: test");
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        let api_error = |code: &str| OciError::ApiError {
+            code: code.to_string(),
+            message: "error".to_string(),
+            details: None,
+            trace: None,
+            retry_after: None,
+        };
+        assert!(api_error("429 Too Many Requests").is_retryable());
+        assert!(api_error("503 Service Unavailable").is_retryable());
+        assert!(!api_error("404 Not Found").is_retryable());
+        assert!(!api_error("501 Not Implemented").is_retryable());
+        assert!(!OciError::Other("error".to_string()).is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_http_status_hint() {
+        let api_error = |code: &str| OciError::ApiError {
+            code: code.to_string(),
+            message: "error".to_string(),
+            details: None,
+            trace: None,
+            retry_after: None,
+        };
+        let status = |status: reqwest::StatusCode| {
+            OciError::from_status_and_body(status, "{}").http_status_hint()
+        };
+
+        // Invalid input
+        assert_eq!(OciError::ConfigError("x".into()).http_status_hint(), 400);
+        assert_eq!(
+            OciError::UnsupportedRegion("x".into()).http_status_hint(),
+            400
+        );
+        assert_eq!(status(reqwest::StatusCode::BAD_REQUEST), 400);
+
+        // Auth
+        assert_eq!(OciError::AuthError("x".into()).http_status_hint(), 401);
+        assert_eq!(status(reqwest::StatusCode::UNAUTHORIZED), 401);
+        assert_eq!(status(reqwest::StatusCode::FORBIDDEN), 403);
+
+        // OCI client errors
+        assert_eq!(status(reqwest::StatusCode::NOT_FOUND), 404);
+        assert_eq!(status(reqwest::StatusCode::CONFLICT), 409);
+        assert_eq!(status(reqwest::StatusCode::PRECONDITION_FAILED), 412);
+        assert_eq!(status(reqwest::StatusCode::TOO_MANY_REQUESTS), 429);
+
+        // OCI server errors
+        assert_eq!(status(reqwest::StatusCode::INTERNAL_SERVER_ERROR), 502);
+        assert_eq!(status(reqwest::StatusCode::SERVICE_UNAVAILABLE), 502);
+        assert_eq!(status(reqwest::StatusCode::GATEWAY_TIMEOUT), 504);
+        assert_eq!(api_error("unknown").http_status_hint(), 502);
+
+        // Local errors
+        assert_eq!(OciError::EnvError("x".into()).http_status_hint(), 500);
+        assert_eq!(OciError::KeyError("x".into()).http_status_hint(), 500);
+        assert_eq!(OciError::IniError("x".into()).http_status_hint(), 500);
+        assert_eq!(OciError::Other("x".into()).http_status_hint(), 500);
+        let io_error = std::io::Error::other("x");
+        assert_eq!(OciError::from(io_error).http_status_hint(), 500);
+
+        // Failed and timed out requests
+        let connect_error = reqwest::get("http://127.0.0.1:1/").await.unwrap_err();
+        assert_eq!(OciError::from(connect_error).http_status_hint(), 502);
+
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::any())
+            .respond_with(
+                wiremock::ResponseTemplate::new(200)
+                    .set_delay(std::time::Duration::from_millis(500)),
+            )
+            .mount(&server)
+            .await;
+        let timeout_error = reqwest::Client::new()
+            .get(server.uri())
+            .timeout(std::time::Duration::from_millis(10))
+            .send()
+            .await
+            .unwrap_err();
+        assert_eq!(OciError::from(timeout_error).http_status_hint(), 504);
+    }
+
     #[test]
     fn test_config_error() {
         let error = OciError::ConfigError("test message".to_string());
@@ -93,11 +513,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_base64_error_from() {
+        use base64::Engine;
+
+        let error: OciError = base64::engine::general_purpose::STANDARD
+            .decode("not base64!")
+            .unwrap_err()
+            .into();
+        assert!(matches!(error, OciError::Base64Error(_)));
+        assert!(std::error::Error::source(&error).is_some());
+    }
+
     #[test]
     fn test_api_error() {
         let error = OciError::ApiError {
             code: "404".to_string(),
             message: "Resource not found".to_string(),
+            details: None,
+            trace: None,
+            retry_after: None,
         };
         assert_eq!(
             error.to_string(),
@@ -105,6 +540,133 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_api_error_body_deserialization() {
+        let body = r#"{
+            "code": "NotAuthorizedOrNotFound",
+            "message": "Authorization failed or requested resource not found.",
+            "originalMessage": "Authorization failed or requested resource not found.",
+            "originalMessageTemplate": "Authorization failed or requested resource not found.",
+            "messageArguments": {"resource": "sender"},
+            "opc-request-id": "ABCDEF123456/0123456789ABCDEF/FEDCBA9876543210"
+        }"#;
+
+        let error = OciError::from_status_and_body(reqwest::StatusCode::NOT_FOUND, body);
+        match error {
+            OciError::ApiError {
+                code,
+                message,
+                details,
+                ..
+            } => {
+                assert_eq!(code, "404 Not Found");
+                assert_eq!(
+                    message,
+                    "Authorization failed or requested resource not found."
+                );
+
+                let details = details.expect("details should be parsed");
+                assert_eq!(details.code.as_deref(), Some("NotAuthorizedOrNotFound"));
+                assert!(details.original_message.is_some());
+                assert!(details.original_message_template.is_some());
+                assert_eq!(
+                    details.message_arguments.unwrap()["resource"],
+                    serde_json::json!("sender")
+                );
+                assert_eq!(
+                    details.opc_request_id.as_deref(),
+                    Some("ABCDEF123456/0123456789ABCDEF/FEDCBA9876543210")
+                );
+            }
+            e => panic!("Expected ApiError, got: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_api_error_non_json_body() {
+        let error =
+            OciError::from_status_and_body(reqwest::StatusCode::BAD_GATEWAY, "<html>oops</html>");
+        match error {
+            OciError::ApiError {
+                message, details, ..
+            } => {
+                assert_eq!(message, "<html>oops</html>");
+                assert!(details.is_none());
+            }
+            e => panic!("Expected ApiError, got: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_precondition_failed() {
+        let body = r#"{"code": "NoEtagMatch", "message": "The resource has changed"}"#;
+        let error = OciError::from_status_and_body(reqwest::StatusCode::PRECONDITION_FAILED, body);
+        match error {
+            OciError::PreconditionFailed {
+                message,
+                details,
+                trace,
+            } => {
+                assert_eq!(message, "The resource has changed");
+                assert_eq!(details.unwrap().code.as_deref(), Some("NoEtagMatch"));
+                assert!(trace.is_none());
+            }
+            e => panic!("Expected PreconditionFailed, got: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_unauthorized_and_forbidden() {
+        let body = r#"{"code": "NotAuthenticated", "message": "The required information to complete authentication was not provided."}"#;
+        let error = OciError::from_status_and_body(reqwest::StatusCode::UNAUTHORIZED, body);
+        assert!(error.is_unauthorized());
+        assert!(!error.is_forbidden());
+        assert!(error.to_string().contains("system clock"));
+
+        let body = r#"{"code": "NotAuthorized", "message": "Not authorized"}"#;
+        let error = OciError::from_status_and_body(reqwest::StatusCode::FORBIDDEN, body)
+            .with_request_context(
+                &reqwest::Method::GET,
+                "/20170907/senders?compartmentId=ocid1.compartment.oc1..a&lifecycleState=ACTIVE",
+                "ocid1.tenancy.oc1..default",
+            );
+        assert!(error.is_forbidden());
+        assert!(!error.is_unauthorized());
+        match &error {
+            OciError::Forbidden {
+                action,
+                compartment,
+                ..
+            } => {
+                assert_eq!(action, "GET /20170907/senders");
+                assert_eq!(compartment, "ocid1.compartment.oc1..a");
+            }
+            e => panic!("Expected Forbidden, got: {:?}", e),
+        }
+        assert!(error.to_string().contains("IAM policy"));
+
+        // Without a compartmentId query parameter, the default compartment is named
+        let error = OciError::from_status_and_body(reqwest::StatusCode::FORBIDDEN, body)
+            .with_request_context(
+                &reqwest::Method::POST,
+                "/20220926/actions/submitEmail",
+                "ocid1.tenancy.oc1..default",
+            );
+        assert!(error.to_string().starts_with(
+            "Not authorized (403) to POST /20220926/actions/submitEmail in compartment ocid1.tenancy.oc1..***ault"
+        ));
+
+        // Other errors are unaffected
+        let error = OciError::from_status_and_body(reqwest::StatusCode::NOT_FOUND, body);
+        assert!(!error.is_unauthorized() && !error.is_forbidden());
+    }
+
+    #[test]
+    fn test_unsupported_region_error() {
+        let error = OciError::UnsupportedRegion("'not a region'".to_string());
+        assert_eq!(error.to_string(), "Unsupported region: 'not a region'");
+    }
+
     #[test]
     fn test_ini_error() {
         let error = OciError::IniError("Failed to parse INI file".to_string());
@@ -32,7 +32,8 @@ pub mod utils;
 // Re-exports for convenient imports
 pub use auth::OciConfig;
 pub use client::OciClient;
-pub use error::{OciError, Result};
+pub use error::{OciApiErrorBody, OciError, RequestTrace, Result};
 
-// Re-export email module to allow `oci_api::email::*` (without `services`)
+// Re-export service modules to allow `oci_api::email::*` (without `services`)
 pub use services::email;
+pub use services::identity;
@@ -1,4 +1,301 @@
 //! Email Delivery API endpoint definitions
 //!
-//! Currently implemented directly in client.rs.
-//! This is a placeholder for future expansion.
+//! Typed requests for each operation: method, path with query string, body and extra
+//! headers. `EmailClient` sends them with `OciClient`, which signs them (so retries,
+//! hooks, interceptors and the fallback key apply). Adding an operation means adding a
+//! request here and a method composing it in `client.rs`.
+//!
+//! ```
+//! # use oci_api::services::email::api::{self, Plane};
+//! let request = api::ListSenders::new("ocid1.compartment.oc1..example")
+//!     .lifecycle_state("ACTIVE")
+//!     .build();
+//!
+//! assert_eq!(request.plane, Plane::Control);
+//! assert_eq!(
+//!     request.path,
+//!     "/20170907/senders?compartmentId=ocid1.compartment.oc1..example&lifecycleState=ACTIVE"
+//! );
+//! ```
+
+use crate::error::Result;
+use crate::utils::encoding::path_with_query;
+use reqwest::Method;
+
+/// Control-plane API version (configuration and senders)
+pub const CONTROL_API_VERSION: &str = "20170907";
+
+/// Submit API version
+pub const SUBMIT_API_VERSION: &str = "20220926";
+
+/// Endpoint a request is sent to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Plane {
+    /// Control plane (`ctrl.email.{region}.oci.oraclecloud.com`, or the `email.control` override)
+    Control,
+    /// Submit endpoint (from the email configuration, or the `email.submit` override)
+    Submit,
+}
+
+/// Email Delivery API request, to be signed and sent by `OciClient`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiRequest {
+    /// HTTP method
+    pub method: Method,
+
+    /// Endpoint the request is sent to
+    pub plane: Plane,
+
+    /// Request path including query string
+    pub path: String,
+
+    /// JSON request body (optional)
+    pub body: Option<String>,
+
+    /// Extra request headers (e.g., `if-match`)
+    pub headers: Vec<(&'static str, String)>,
+}
+
+impl ApiRequest {
+    fn new(method: Method, plane: Plane, path: String) -> Self {
+        Self {
+            method,
+            plane,
+            path,
+            body: None,
+            headers: Vec::new(),
+        }
+    }
+
+    /// Extra headers as borrowed `(name, value)` pairs
+    pub fn header_pairs(&self) -> Vec<(&str, &str)> {
+        self.headers
+            .iter()
+            .map(|(name, value)| (*name, value.as_str()))
+            .collect()
+    }
+}
+
+/// GetEmailConfiguration request
+///
+/// # Arguments
+/// * `compartment_id` - Compartment OCID (typically tenancy OCID)
+pub fn get_configuration(compartment_id: &str) -> ApiRequest {
+    let path = path_with_query(
+        &format!("/{}/configuration", CONTROL_API_VERSION),
+        &[("compartmentId", compartment_id)],
+    );
+    ApiRequest::new(Method::GET, Plane::Control, path)
+}
+
+/// SubmitEmail request
+///
+/// # Arguments
+/// * `body_json` - Serialized SubmitEmailDetails body
+pub fn submit_email(body_json: String) -> ApiRequest {
+    let path = format!("/{}/actions/submitEmail", SUBMIT_API_VERSION);
+    ApiRequest {
+        body: Some(body_json),
+        ..ApiRequest::new(Method::POST, Plane::Submit, path)
+    }
+}
+
+/// ListSenders request builder
+#[derive(Debug, Clone)]
+pub struct ListSenders<'a> {
+    compartment_id: &'a str,
+    lifecycle_state: Option<&'a str>,
+    email_address: Option<&'a str>,
+    page: Option<&'a str>,
+}
+
+impl<'a> ListSenders<'a> {
+    /// List senders of `compartment_id`
+    pub fn new(compartment_id: &'a str) -> Self {
+        Self {
+            compartment_id,
+            lifecycle_state: None,
+            email_address: None,
+            page: None,
+        }
+    }
+
+    /// Filter by lifecycle state (e.g., `ACTIVE`)
+    pub fn lifecycle_state(mut self, state: &'a str) -> Self {
+        self.lifecycle_state = Some(state);
+        self
+    }
+
+    /// Filter by email address
+    pub fn email_address(mut self, email_address: &'a str) -> Self {
+        self.email_address = Some(email_address);
+        self
+    }
+
+    /// Page cursor from the `opc-next-page` header of the previous page
+    pub fn page(mut self, page: &'a str) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    /// Build the request
+    pub fn build(self) -> ApiRequest {
+        let mut query = vec![("compartmentId", self.compartment_id)];
+
+        if let Some(state) = self.lifecycle_state {
+            query.push(("lifecycleState", state));
+        }
+
+        if let Some(email) = self.email_address {
+            query.push(("emailAddress", email));
+        }
+
+        if let Some(page) = self.page {
+            query.push(("page", page));
+        }
+
+        let path = path_with_query(&format!("/{}/senders", CONTROL_API_VERSION), &query);
+        ApiRequest::new(Method::GET, Plane::Control, path)
+    }
+}
+
+/// GetSender request
+///
+/// # Arguments
+/// * `sender_id` - Sender OCID
+pub fn get_sender(sender_id: &str) -> ApiRequest {
+    ApiRequest::new(Method::GET, Plane::Control, sender_path(sender_id))
+}
+
+/// CreateSender request
+///
+/// # Arguments
+/// * `compartment_id` - Compartment OCID of the sender
+/// * `email_address` - Sender email address
+pub fn create_sender(compartment_id: &str, email_address: &str) -> Result<ApiRequest> {
+    let body = serde_json::json!({
+        "compartmentId": compartment_id,
+        "emailAddress": email_address,
+    });
+    let path = format!("/{}/senders", CONTROL_API_VERSION);
+    Ok(ApiRequest {
+        body: Some(serde_json::to_string(&body)?),
+        ..ApiRequest::new(Method::POST, Plane::Control, path)
+    })
+}
+
+/// DeleteSender request
+///
+/// # Arguments
+/// * `sender_id` - Sender OCID
+/// * `if_match` - Optional ETag (sent as `if-match`)
+pub fn delete_sender(sender_id: &str, if_match: Option<&str>) -> ApiRequest {
+    ApiRequest {
+        headers: if_match
+            .map(|etag| ("if-match", etag.to_string()))
+            .into_iter()
+            .collect(),
+        ..ApiRequest::new(Method::DELETE, Plane::Control, sender_path(sender_id))
+    }
+}
+
+/// Path of a sender
+fn sender_path(sender_id: &str) -> String {
+    format!("/{}/senders/{}", CONTROL_API_VERSION, sender_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_configuration() {
+        let request = get_configuration("ocid1.tenancy.oc1..test");
+        assert_eq!(request.method, Method::GET);
+        assert_eq!(request.plane, Plane::Control);
+        assert_eq!(
+            request.path,
+            "/20170907/configuration?compartmentId=ocid1.tenancy.oc1..test"
+        );
+        assert!(request.body.is_none());
+
+        let request = get_configuration("ocid1 test&x=y");
+        assert_eq!(
+            request.path,
+            "/20170907/configuration?compartmentId=ocid1%20test%26x%3Dy"
+        );
+    }
+
+    #[test]
+    fn test_submit_email() {
+        let request = submit_email("{}".to_string());
+        assert_eq!(request.method, Method::POST);
+        assert_eq!(request.plane, Plane::Submit);
+        assert_eq!(request.path, "/20220926/actions/submitEmail");
+        assert_eq!(request.body.as_deref(), Some("{}"));
+    }
+
+    #[test]
+    fn test_list_senders() {
+        let request = ListSenders::new("ocid1.tenancy.oc1..test").build();
+        assert_eq!(
+            request.path,
+            "/20170907/senders?compartmentId=ocid1.tenancy.oc1..test"
+        );
+
+        let request = ListSenders::new("ocid1.compartment.oc1..test")
+            .lifecycle_state("ACTIVE")
+            .email_address("noreply@example.com")
+            .build();
+        assert_eq!(request.method, Method::GET);
+        assert_eq!(
+            request.path,
+            "/20170907/senders?compartmentId=ocid1.compartment.oc1..test&emailAddress=noreply%40example.com&lifecycleState=ACTIVE"
+        );
+
+        let request = ListSenders::new("ocid1.tenancy.oc1..test")
+            .page("a+b/c==")
+            .build();
+        assert_eq!(
+            request.path,
+            "/20170907/senders?compartmentId=ocid1.tenancy.oc1..test&page=a%2Bb%2Fc%3D%3D"
+        );
+    }
+
+    #[test]
+    fn test_get_sender() {
+        let request = get_sender("ocid1.emailsender.oc1..test");
+        assert_eq!(request.method, Method::GET);
+        assert_eq!(
+            request.path,
+            "/20170907/senders/ocid1.emailsender.oc1..test"
+        );
+    }
+
+    #[test]
+    fn test_create_sender() {
+        let request = create_sender("ocid1.compartment.oc1..test", "noreply@example.com").unwrap();
+        assert_eq!(request.method, Method::POST);
+        assert_eq!(request.plane, Plane::Control);
+        assert_eq!(request.path, "/20170907/senders");
+
+        let body: serde_json::Value =
+            serde_json::from_str(request.body.as_deref().unwrap()).unwrap();
+        assert_eq!(body["compartmentId"], "ocid1.compartment.oc1..test");
+        assert_eq!(body["emailAddress"], "noreply@example.com");
+    }
+
+    #[test]
+    fn test_delete_sender() {
+        let request = delete_sender("ocid1.emailsender.oc1..test", None);
+        assert_eq!(request.method, Method::DELETE);
+        assert_eq!(
+            request.path,
+            "/20170907/senders/ocid1.emailsender.oc1..test"
+        );
+        assert!(request.header_pairs().is_empty());
+
+        let request = delete_sender("ocid1.emailsender.oc1..test", Some("etag-1"));
+        assert_eq!(request.header_pairs(), [("if-match", "etag-1")]);
+    }
+}
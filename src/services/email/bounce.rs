@@ -0,0 +1,239 @@
+//! Bounce and complaint event models
+//!
+//! Deserialization models for Email Delivery outbound events (OCI Logging
+//! `emailoutboundrelayed` records), as delivered to a webhook by OCI Notifications or
+//! Service Connector Hub:
+//!
+//! ```
+//! # use oci_api::services::email::bounce::{BounceAction, BounceEvent};
+//! let event: BounceEvent = serde_json::from_str(r#"{
+//!     "type": "com.oraclecloud.emaildelivery.emailoutboundrelayed",
+//!     "time": "2024-05-01T12:00:00.000Z",
+//!     "data": {
+//!         "action": "bounce",
+//!         "messageId": "<abc@example.com>",
+//!         "recipient": "nobody@example.org",
+//!         "bounceCategory": "bad-mailbox",
+//!         "smtpStatus": "550 5.1.1 user unknown"
+//!     }
+//! }"#).unwrap();
+//!
+//! assert_eq!(event.data.action, BounceAction::Bounce);
+//! assert_eq!(event.recipient(), "nobody@example.org");
+//! ```
+//!
+//! Only `data.messageId` and `data.recipient` are required; other fields are optional
+//! so that events with a partial or newer shape still parse.
+
+use serde::{Deserialize, Serialize};
+
+/// Email Delivery outbound event (bounce, complaint or relay)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BounceEvent {
+    /// Event ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// Event type (e.g., `com.oraclecloud.emaildelivery.emailoutboundrelayed`)
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub event_type: Option<String>,
+
+    /// Event source (e.g., the log OCID)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    /// Event time (RFC 3339)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<String>,
+
+    /// Event payload
+    pub data: BounceEventData,
+}
+
+impl BounceEvent {
+    /// Message ID of the email the event is about
+    pub fn message_id(&self) -> &str {
+        &self.data.message_id
+    }
+
+    /// Recipient the event is about
+    pub fn recipient(&self) -> &str {
+        &self.data.recipient
+    }
+
+    /// Bounce reason: the bounce category, or the error type if not set
+    pub fn reason(&self) -> Option<&str> {
+        self.data
+            .bounce_category
+            .as_deref()
+            .or(self.data.error_type.as_deref())
+    }
+
+    /// Diagnostic code: the SMTP status line, or the bounce code if not set
+    pub fn diagnostic_code(&self) -> Option<&str> {
+        self.data
+            .smtp_status
+            .as_deref()
+            .or(self.data.bounce_code.as_deref())
+    }
+
+    /// Event time, or the time the email was received if not set
+    pub fn timestamp(&self) -> Option<&str> {
+        self.time.as_deref().or(self.data.received_time.as_deref())
+    }
+
+    /// Whether the event is a bounce
+    pub fn is_bounce(&self) -> bool {
+        self.data.action == BounceAction::Bounce
+    }
+
+    /// Whether the event is a complaint (recipient marked the email as spam)
+    pub fn is_complaint(&self) -> bool {
+        self.data.action == BounceAction::Complaint
+    }
+}
+
+/// Email Delivery outbound event payload
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BounceEventData {
+    /// What happened to the email
+    #[serde(default)]
+    pub action: BounceAction,
+
+    /// Message ID of the email
+    #[serde(rename = "messageId")]
+    pub message_id: String,
+
+    /// Recipient address
+    pub recipient: String,
+
+    /// Sender address (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sender: Option<String>,
+
+    /// Sender compartment ID (optional)
+    #[serde(
+        rename = "senderCompartmentId",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub sender_compartment_id: Option<String>,
+
+    /// Bounce category (e.g., `bad-mailbox`, `policy-related`)
+    #[serde(rename = "bounceCategory", skip_serializing_if = "Option::is_none")]
+    pub bounce_category: Option<String>,
+
+    /// Enhanced status code of the bounce (e.g., `5.1.1`)
+    #[serde(rename = "bounceCode", skip_serializing_if = "Option::is_none")]
+    pub bounce_code: Option<String>,
+
+    /// SMTP status line returned by the receiving server
+    #[serde(rename = "smtpStatus", skip_serializing_if = "Option::is_none")]
+    pub smtp_status: Option<String>,
+
+    /// Error type (e.g., `hard`, `soft`)
+    #[serde(rename = "errorType", skip_serializing_if = "Option::is_none")]
+    pub error_type: Option<String>,
+
+    /// Time the email was received by Email Delivery (RFC 3339)
+    #[serde(rename = "receivedTime", skip_serializing_if = "Option::is_none")]
+    pub received_time: Option<String>,
+}
+
+/// Outbound event action
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BounceAction {
+    /// Accepted by the receiving server
+    Relay,
+    /// Rejected by the receiving server (hard or soft bounce)
+    Bounce,
+    /// Reported as spam by the recipient
+    Complaint,
+    /// Action not known to this crate (or not set)
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_bounce_event() {
+        let json = r#"{
+            "id": "2c6f5f0a-1c3a-4a5e-9f2d-0d4f1b6c7e8a",
+            "type": "com.oraclecloud.emaildelivery.emailoutboundrelayed",
+            "source": "ocid1.log.oc1.ap-seoul-1.test",
+            "specversion": "1.0",
+            "time": "2024-05-01T12:00:03.512Z",
+            "data": {
+                "action": "bounce",
+                "messageId": "<0100018f2a@example.com>",
+                "recipient": "nobody@example.org",
+                "recipientMailDomain": "example.org",
+                "sender": "noreply@example.com",
+                "senderCompartmentId": "ocid1.compartment.oc1..test",
+                "bounceCategory": "bad-mailbox",
+                "bounceCode": "5.1.1",
+                "smtpStatus": "550 5.1.1 <nobody@example.org>: Recipient address rejected",
+                "errorType": "hard",
+                "receivedTime": "2024-05-01T12:00:01.000Z"
+            }
+        }"#;
+
+        let event: BounceEvent = serde_json::from_str(json).unwrap();
+        assert!(event.is_bounce());
+        assert!(!event.is_complaint());
+        assert_eq!(event.message_id(), "<0100018f2a@example.com>");
+        assert_eq!(event.recipient(), "nobody@example.org");
+        assert_eq!(event.reason(), Some("bad-mailbox"));
+        assert_eq!(
+            event.diagnostic_code(),
+            Some("550 5.1.1 <nobody@example.org>: Recipient address rejected")
+        );
+        assert_eq!(event.timestamp(), Some("2024-05-01T12:00:03.512Z"));
+        assert_eq!(event.data.bounce_code.as_deref(), Some("5.1.1"));
+        assert_eq!(
+            event.data.sender_compartment_id.as_deref(),
+            Some("ocid1.compartment.oc1..test")
+        );
+    }
+
+    #[test]
+    fn test_deserialize_minimal_complaint_event() {
+        let json = r#"{
+            "data": {
+                "action": "complaint",
+                "messageId": "<id@example.com>",
+                "recipient": "user@example.org",
+                "receivedTime": "2024-05-01T12:00:01.000Z"
+            }
+        }"#;
+
+        let event: BounceEvent = serde_json::from_str(json).unwrap();
+        assert!(event.is_complaint());
+        assert_eq!(event.reason(), None);
+        assert_eq!(event.diagnostic_code(), None);
+        assert_eq!(event.timestamp(), Some("2024-05-01T12:00:01.000Z"));
+    }
+
+    #[test]
+    fn test_unknown_or_missing_action() {
+        let data: BounceEventData = serde_json::from_str(
+            r#"{"action": "deferred", "messageId": "m", "recipient": "r@example.org"}"#,
+        )
+        .unwrap();
+        assert_eq!(data.action, BounceAction::Unknown);
+
+        let data: BounceEventData =
+            serde_json::from_str(r#"{"messageId": "m", "recipient": "r@example.org"}"#).unwrap();
+        assert_eq!(data.action, BounceAction::Unknown);
+    }
+
+    #[test]
+    fn test_missing_recipient_is_error() {
+        let result = serde_json::from_str::<BounceEvent>(r#"{"data": {"messageId": "m"}}"#);
+        assert!(result.is_err());
+    }
+}
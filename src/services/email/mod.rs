@@ -1,7 +1,14 @@
 // Email Delivery 서비스 모듈
 pub mod api;
+pub mod bounce;
 pub mod client;
 pub mod models;
+pub mod stats;
+pub mod templates;
 
+pub use bounce::{BounceAction, BounceEvent, BounceEventData};
 pub use client::EmailClient;
 pub use models::*;
+pub use stats::{EmailStatsClient, SendingStats};
+pub use templates::Template;
+pub use tokio_util::sync::CancellationToken;
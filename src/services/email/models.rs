@@ -1,6 +1,9 @@
 //! Email Delivery API data models
 
+use super::templates::Template;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
 
 /// Email Configuration response
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,7 +25,97 @@ pub struct EmailConfiguration {
     pub email_delivery_config_id: Option<String>,
 }
 
+/// SMTP submission port with STARTTLS (default)
+pub const SMTP_SUBMISSION_PORT: u16 = 587;
+
+impl EmailConfiguration {
+    /// SMTP client settings for `smtp_submit_endpoint` with SMTP credentials
+    ///
+    /// Bridges to SMTP libraries (e.g., `lettre`) without depending on them.
+    /// The endpoint may be a host, `host:port` or `smtp://host[:port]`; without a port,
+    /// the submission port 587 with STARTTLS is used. Port 25 also uses STARTTLS;
+    /// any other explicit port (e.g., 465) is assumed to be implicit TLS.
+    ///
+    /// # Arguments
+    /// * `username` - SMTP credential username (generated in the OCI console)
+    /// * `password` - SMTP credential password
+    pub fn smtp_settings(
+        &self,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> crate::error::Result<SmtpSettings> {
+        let endpoint = self.smtp_submit_endpoint.trim();
+        let endpoint = endpoint
+            .strip_prefix("smtp://")
+            .unwrap_or(endpoint)
+            .trim_end_matches('/');
+
+        let (host, port) = match endpoint.rsplit_once(':') {
+            Some((host, port)) => {
+                let port = port.parse::<u16>().map_err(|_| {
+                    crate::error::OciError::ConfigError(format!(
+                        "Invalid SMTP submit endpoint port: '{}'",
+                        self.smtp_submit_endpoint
+                    ))
+                })?;
+                (host, port)
+            }
+            None => (endpoint, SMTP_SUBMISSION_PORT),
+        };
+
+        if host.is_empty() {
+            return Err(crate::error::OciError::ConfigError(format!(
+                "Invalid SMTP submit endpoint: '{}'",
+                self.smtp_submit_endpoint
+            )));
+        }
+
+        Ok(SmtpSettings {
+            host: host.to_string(),
+            port,
+            starttls: port == SMTP_SUBMISSION_PORT || port == 25,
+            username: username.into(),
+            password: password.into(),
+        })
+    }
+}
+
+/// SMTP client settings (see `EmailConfiguration::smtp_settings`)
+#[derive(Clone, PartialEq, Eq)]
+pub struct SmtpSettings {
+    /// SMTP host (e.g., `smtp.email.ap-seoul-1.oci.oraclecloud.com`)
+    pub host: String,
+
+    /// SMTP port
+    pub port: u16,
+
+    /// Upgrade the connection with STARTTLS (`false` means implicit TLS)
+    pub starttls: bool,
+
+    /// SMTP credential username
+    pub username: String,
+
+    /// SMTP credential password
+    pub password: String,
+}
+
+impl std::fmt::Debug for SmtpSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SmtpSettings")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("starttls", &self.starttls)
+            .field("username", &self.username)
+            .field("password", &"[REDACTED]")
+            .finish()
+    }
+}
+
 /// Email message
+///
+/// Equality compares all fields, so an email equals its serde round trip. Addresses
+/// compare by normalized email (see `EmailAddress`) and display name, and custom headers
+/// regardless of insertion order.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Email {
     /// Message ID (optional)
@@ -46,7 +139,10 @@ pub struct Email {
     #[serde(rename = "bodyText", skip_serializing_if = "Option::is_none")]
     pub body_text: Option<String>,
 
-    /// Reply-To address (optional)
+    /// Reply-To addresses (optional)
+    ///
+    /// OCI puts all addresses in a single `Reply-To` header; most mail clients then reply
+    /// to every address. Replies go to the sender if not set.
     #[serde(rename = "replyTo", skip_serializing_if = "Option::is_none")]
     pub reply_to: Option<Vec<EmailAddress>>,
 
@@ -84,12 +180,68 @@ impl Sender {
         }
     }
 
+    /// Create sender from an approved sender (e.g., from `list_senders`)
+    ///
+    /// Uses the approved sender's compartment if the summary includes it; otherwise
+    /// compartment_id will be set by EmailClient.
+    pub fn from_summary(summary: &SenderSummary) -> Self {
+        Self {
+            sender_address: EmailAddress::from(summary),
+            compartment_id: summary.compartment_id.clone().unwrap_or_default(),
+        }
+    }
+
+    /// Create sender with name from an approved sender (see `from_summary`)
+    pub fn from_summary_with_name(summary: &SenderSummary, name: impl Into<String>) -> Self {
+        Self {
+            sender_address: EmailAddress::with_name(&summary.email_address, name),
+            ..Self::from_summary(summary)
+        }
+    }
+
     /// Internal method to set compartment_id (used by EmailClient)
     pub(crate) fn set_compartment_id(&mut self, compartment_id: impl Into<String>) {
         self.compartment_id = compartment_id.into();
     }
 }
 
+/// Email importance (priority)
+///
+/// Sets the standard priority headers (`X-Priority`, `Importance`, `X-MSMail-Priority`)
+/// consistently, so mail clients agree on the priority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Importance {
+    /// High importance
+    High,
+    /// Normal importance
+    Normal,
+    /// Low importance
+    Low,
+}
+
+impl Importance {
+    /// Header name/value pairs for this importance level
+    pub fn headers(&self) -> [(&'static str, &'static str); 3] {
+        match self {
+            Importance::High => [
+                ("X-Priority", "1 (Highest)"),
+                ("Importance", "High"),
+                ("X-MSMail-Priority", "High"),
+            ],
+            Importance::Normal => [
+                ("X-Priority", "3 (Normal)"),
+                ("Importance", "Normal"),
+                ("X-MSMail-Priority", "Normal"),
+            ],
+            Importance::Low => [
+                ("X-Priority", "5 (Lowest)"),
+                ("Importance", "Low"),
+                ("X-MSMail-Priority", "Low"),
+            ],
+        }
+    }
+}
+
 /// Email address
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmailAddress {
@@ -101,35 +253,38 @@ pub struct EmailAddress {
     pub name: Option<String>,
 }
 
-// Implement PartialEq based on email only (ignore name for equality)
+// Implement PartialEq based on the normalized email only (ignore name and domain case)
 impl PartialEq for EmailAddress {
     fn eq(&self, other: &Self) -> bool {
-        self.email == other.email
+        self.normalized() == other.normalized()
     }
 }
 
 impl Eq for EmailAddress {}
 
-// Implement Hash based on email only (ignore name for hashing)
+// Implement Hash based on the normalized email only (consistent with PartialEq)
 impl std::hash::Hash for EmailAddress {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.email.hash(state);
+        self.normalized().hash(state);
     }
 }
 
 /// Recipients list
+///
+/// Empty lists (e.g. `to: Some(vec![])`) are omitted when serialized, like `None`:
+/// OCI treats an empty array differently from an absent field.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Recipients {
     /// To recipients
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "is_none_or_empty")]
     pub to: Option<Vec<EmailAddress>>,
 
     /// CC recipients
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "is_none_or_empty")]
     pub cc: Option<Vec<EmailAddress>>,
 
     /// BCC recipients
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "is_none_or_empty")]
     pub bcc: Option<Vec<EmailAddress>>,
 }
 
@@ -150,6 +305,123 @@ pub struct SubmitEmailResponse {
         skip_serializing_if = "Option::is_none"
     )]
     pub suppressed_recipients: Option<Vec<EmailAddress>>,
+
+    /// Response fields not known to this crate, kept for audit logging
+    #[serde(flatten, default, skip_serializing_if = "HashMap::is_empty")]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl SubmitEmailResponse {
+    /// Number of suppressed recipients
+    pub fn suppressed_count(&self) -> usize {
+        self.suppressed_recipients.as_ref().map_or(0, Vec::len)
+    }
+
+    /// Whether no recipient was suppressed
+    ///
+    /// This only means OCI accepted the email for every recipient;
+    /// delivery itself may still fail later (e.g. bounces).
+    pub fn was_fully_delivered(&self) -> bool {
+        self.suppressed_count() == 0
+    }
+
+    /// Recipients of `original` (To, CC, then BCC) that were not suppressed
+    ///
+    /// Addresses are compared by `EmailAddress::normalized` (case-insensitive domain).
+    pub fn delivered_recipients(&self, original: &Recipients) -> Vec<EmailAddress> {
+        let suppressed: std::collections::HashSet<Cow<'_, str>> = self
+            .suppressed_recipients
+            .iter()
+            .flatten()
+            .map(EmailAddress::normalized)
+            .collect();
+
+        [&original.to, &original.cc, &original.bcc]
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|addr| !suppressed.contains(&addr.normalized()))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Per-recipient verdict of a submission (see `EmailClient::send_detailed`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeliveryOutcome {
+    /// Recipients accepted for delivery (original recipients that were not suppressed)
+    pub accepted: Vec<EmailAddress>,
+
+    /// Recipients suppressed by OCI (e.g. previous bounces or complaints)
+    pub suppressed: Vec<EmailAddress>,
+
+    /// Submitted email's message ID
+    pub message_id: String,
+
+    /// Envelope ID
+    pub envelope_id: String,
+}
+
+impl DeliveryOutcome {
+    /// Build outcome from a submit response and the recipients that were sent to
+    pub fn from_response(response: SubmitEmailResponse, original: &Recipients) -> Self {
+        let accepted = response.delivered_recipients(original);
+
+        Self {
+            accepted,
+            suppressed: response.suppressed_recipients.unwrap_or_default(),
+            message_id: response.message_id,
+            envelope_id: response.envelope_id,
+        }
+    }
+
+    /// Whether every recipient was accepted
+    pub fn is_fully_accepted(&self) -> bool {
+        self.suppressed.is_empty()
+    }
+
+    /// Whether some recipients were accepted and others suppressed
+    pub fn is_partial(&self) -> bool {
+        !self.accepted.is_empty() && !self.suppressed.is_empty()
+    }
+}
+
+/// Failed send, with the email given back for a retry or a dead-letter queue
+///
+/// Returned by `EmailClient::send`, which consumes the email. Displays as the
+/// underlying error; converts into `OciError` with `?`.
+#[derive(Debug)]
+pub struct SendError {
+    /// The email as passed to `send` (not modified)
+    pub email: Box<Email>,
+
+    /// Why the send failed
+    pub source: crate::error::OciError,
+}
+
+impl SendError {
+    /// Email and error
+    pub fn into_parts(self) -> (Email, crate::error::OciError) {
+        (*self.email, self.source)
+    }
+}
+
+impl std::fmt::Display for SendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.source.fmt(f)
+    }
+}
+
+impl std::error::Error for SendError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source.source()
+    }
+}
+
+impl From<SendError> for crate::error::OciError {
+    fn from(error: SendError) -> Self {
+        error.source
+    }
 }
 
 /// Sender summary from list_senders API
@@ -177,11 +449,85 @@ pub struct SenderSummary {
     /// Compartment ID (optional, not always included)
     #[serde(rename = "compartmentId", skip_serializing_if = "Option::is_none")]
     pub compartment_id: Option<String>,
+
+    /// ETag from the `etag` response header (set by `get_sender`, `None` in list results)
+    #[serde(skip)]
+    pub etag: Option<String>,
+}
+
+/// Filter for `EmailClient::find_senders`
+///
+/// All set criteria must match. `state` is also applied server-side; `spf_required` and
+/// `domain` are applied client-side.
+///
+/// # Example
+/// ```
+/// # use oci_api::email::{SenderFilter, SenderLifecycleState};
+/// let filter = SenderFilter::new()
+///     .state(SenderLifecycleState::Active)
+///     .spf_required(true)
+///     .domain("example.com");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SenderFilter {
+    /// Lifecycle state the sender must be in
+    pub state: Option<SenderLifecycleState>,
+
+    /// Keep only senders with SPF configured (`is_spf == Some(true)`)
+    pub spf_required: bool,
+
+    /// Domain of the sender address (compared case-insensitively)
+    pub domain: Option<String>,
+}
+
+impl SenderFilter {
+    /// Filter matching every sender
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep only senders in `state`
+    pub fn state(mut self, state: SenderLifecycleState) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    /// Keep only senders with SPF configured
+    pub fn spf_required(mut self, required: bool) -> Self {
+        self.spf_required = required;
+        self
+    }
+
+    /// Keep only senders whose address is in `domain`
+    pub fn domain(mut self, domain: impl Into<String>) -> Self {
+        self.domain = Some(domain.into());
+        self
+    }
+
+    /// Whether `sender` matches every set criterion
+    pub fn matches(&self, sender: &SenderSummary) -> bool {
+        let state_matches = self
+            .state
+            .as_ref()
+            .is_none_or(|state| *state == sender.lifecycle_state);
+        let spf_matches = !self.spf_required || sender.is_spf == Some(true);
+        let domain_matches = self.domain.as_deref().is_none_or(|domain| {
+            sender
+                .email_address
+                .rsplit_once('@')
+                .is_some_and(|(_, sender_domain)| sender_domain.eq_ignore_ascii_case(domain))
+        });
+
+        state_matches && spf_matches && domain_matches
+    }
 }
 
 /// Sender lifecycle state
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+///
+/// States added by OCI after this crate was released deserialize as `Unknown` (keeping
+/// the raw value) instead of failing the whole response. Since 0.2.0, exhaustive matches
+/// need an `Unknown(_)` arm, and the state is `Clone` but not `Copy`.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SenderLifecycleState {
     /// Creating
     Creating,
@@ -197,6 +543,101 @@ pub enum SenderLifecycleState {
     Deleting,
     /// Deleted
     Deleted,
+    /// State unknown to this crate (raw OCI value)
+    Unknown(String),
+}
+
+impl SenderLifecycleState {
+    /// All known lifecycle states
+    pub const ALL: [SenderLifecycleState; 7] = [
+        SenderLifecycleState::Creating,
+        SenderLifecycleState::Active,
+        SenderLifecycleState::NeedsAttention,
+        SenderLifecycleState::Inactive,
+        SenderLifecycleState::Failed,
+        SenderLifecycleState::Deleting,
+        SenderLifecycleState::Deleted,
+    ];
+
+    /// OCI wire string (e.g., `ACTIVE`)
+    pub fn as_str(&self) -> &str {
+        match self {
+            SenderLifecycleState::Creating => "CREATING",
+            SenderLifecycleState::Active => "ACTIVE",
+            SenderLifecycleState::NeedsAttention => "NEEDS_ATTENTION",
+            SenderLifecycleState::Inactive => "INACTIVE",
+            SenderLifecycleState::Failed => "FAILED",
+            SenderLifecycleState::Deleting => "DELETING",
+            SenderLifecycleState::Deleted => "DELETED",
+            SenderLifecycleState::Unknown(state) => state,
+        }
+    }
+
+    /// Whether the sender can send (`ACTIVE`)
+    pub fn is_active(&self) -> bool {
+        *self == SenderLifecycleState::Active
+    }
+
+    /// Whether the sender will not change state anymore (`FAILED` or `DELETED`)
+    ///
+    /// `Unknown` states are not considered terminal.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            SenderLifecycleState::Failed | SenderLifecycleState::Deleted
+        )
+    }
+}
+
+impl Serialize for SenderLifecycleState {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for SenderLifecycleState {
+    /// Known states by wire string, anything else as `Unknown`
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let state = String::deserialize(deserializer)?;
+        Ok(state
+            .parse()
+            .unwrap_or(SenderLifecycleState::Unknown(state)))
+    }
+}
+
+impl std::fmt::Display for SenderLifecycleState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for SenderLifecycleState {
+    type Err = crate::error::OciError;
+
+    /// Parse OCI wire string of a known state (e.g., `ACTIVE`)
+    fn from_str(s: &str) -> crate::error::Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|state| state.as_str() == s)
+            .ok_or_else(|| {
+                crate::error::OciError::ConfigError(format!(
+                    "Unknown sender lifecycle state: {}",
+                    s
+                ))
+            })
+    }
+}
+
+impl From<&SenderSummary> for EmailAddress {
+    /// Address of an approved sender, without name
+    fn from(summary: &SenderSummary) -> Self {
+        Self::new(&summary.email_address)
+    }
 }
 
 impl EmailAddress {
@@ -215,16 +656,114 @@ impl EmailAddress {
             name: Some(name.into()),
         }
     }
+
+    /// Local part (before the last `@`), or `None` if the address has no `@`
+    pub fn local_part(&self) -> Option<&str> {
+        self.email.rsplit_once('@').map(|(local, _)| local)
+    }
+
+    /// Address with the domain lowercased, as used for equality and deduplication
+    ///
+    /// Domains are case-insensitive, so `User@Example.com` and `User@example.com` are the
+    /// same mailbox. The local part is kept as is: RFC 5321 allows it to be case-sensitive,
+    /// although almost no mail server treats it that way (see
+    /// `RecipientsBuilder::case_insensitive_local_part`).
+    pub fn normalized(&self) -> Cow<'_, str> {
+        match self.email.rsplit_once('@') {
+            Some((local, domain)) if domain.chars().any(|c| c.is_uppercase()) => {
+                Cow::Owned(format!("{}@{}", local, domain.to_lowercase()))
+            }
+            _ => Cow::Borrowed(&self.email),
+        }
+    }
+
+    /// Domain part (after the last `@`), or `None` if the address has no `@` or an empty domain
+    pub fn domain(&self) -> Option<&str> {
+        self.email
+            .rsplit_once('@')
+            .map(|(_, domain)| domain)
+            .filter(|domain| !domain.is_empty())
+    }
+
+    /// Whether the address looks deliverable: `local@domain` with a non-empty local part
+    /// without whitespace and a valid (possibly international) domain with at least one dot
+    ///
+    /// A syntax check only; it does not check that the mailbox exists.
+    pub fn is_valid(&self) -> bool {
+        let Some((local, domain)) = self.email.rsplit_once('@') else {
+            return false;
+        };
+        let Ok(ascii) = self.ascii_email() else {
+            return false;
+        };
+        let domain = ascii.rsplit_once('@').map_or(domain, |(_, d)| d);
+
+        !local.is_empty()
+            && local.len() <= 64
+            && !local.chars().any(char::is_whitespace)
+            && domain.contains('.')
+            && is_valid_domain(domain)
+    }
+
+    /// Address with an international domain converted to ASCII (punycode)
+    ///
+    /// e.g. `user@müller.de` becomes `user@xn--mller-kva.de`. ASCII domains are returned
+    /// as-is; the local part is never changed. Returns `OciError::ConfigError` if the
+    /// domain is not a valid international domain name.
+    pub fn ascii_email(&self) -> crate::error::Result<Cow<'_, str>> {
+        match self.email.rsplit_once('@') {
+            Some((local, domain)) if !domain.is_ascii() => {
+                let domain = idna::domain_to_ascii(domain).map_err(|_| {
+                    crate::error::OciError::ConfigError(format!(
+                        "Invalid international domain in '{}'",
+                        self.email
+                    ))
+                })?;
+                Ok(Cow::Owned(format!("{}@{}", local, domain)))
+            }
+            _ => Ok(Cow::Borrowed(&self.email)),
+        }
+    }
+
+    /// Group addresses by lowercased domain
+    ///
+    /// Addresses without a domain are grouped under an empty string key.
+    /// The order of addresses within each group is preserved.
+    pub fn group_by_domain(addresses: &[EmailAddress]) -> HashMap<String, Vec<EmailAddress>> {
+        let mut groups: HashMap<String, Vec<EmailAddress>> = HashMap::new();
+        for address in addresses {
+            let domain = address.domain().unwrap_or_default().to_lowercase();
+            groups.entry(domain).or_default().push(address.clone());
+        }
+        groups
+    }
 }
 
 impl Recipients {
-    /// Remove duplicates from email address list
+    /// Remove duplicates from email address list (domain compared case-insensitively)
     fn deduplicate(addresses: Vec<EmailAddress>) -> Vec<EmailAddress> {
+        Self::deduplicate_with(addresses, false)
+    }
+
+    /// Remove duplicates, keeping the first occurrence
+    ///
+    /// With `case_insensitive_local`, the local part is compared case-insensitively too.
+    fn deduplicate_with(
+        addresses: Vec<EmailAddress>,
+        case_insensitive_local: bool,
+    ) -> Vec<EmailAddress> {
         use std::collections::HashSet;
         let mut seen = HashSet::new();
         addresses
             .into_iter()
-            .filter(|addr| seen.insert(addr.clone()))
+            .filter(|addr| {
+                let key = if case_insensitive_local {
+                    addr.email.to_lowercase()
+                } else {
+                    addr.normalized().into_owned()
+                };
+                seen.insert(key)
+            })
             .collect()
     }
 
@@ -242,6 +781,18 @@ impl Recipients {
         }
     }
 
+    /// Create recipients list with only To recipients, keeping duplicates
+    ///
+    /// Unlike `to`, addresses are not deduplicated. Use this only when duplicates are
+    /// intentional (e.g. debugging list expansion); OCI may deliver one copy per entry.
+    pub fn to_raw(addresses: Vec<EmailAddress>) -> Self {
+        Self {
+            to: Some(addresses),
+            cc: None,
+            bcc: None,
+        }
+    }
+
     /// Create recipients list with only CC recipients
     pub fn cc(addresses: Vec<EmailAddress>) -> Self {
         Self {
@@ -293,6 +844,21 @@ impl Recipients {
         self
     }
 
+    /// Number of To, CC and BCC recipients
+    pub fn count(&self) -> usize {
+        [&self.to, &self.cc, &self.bcc]
+            .into_iter()
+            .flatten()
+            .map(Vec::len)
+            .sum()
+    }
+
+    /// Whether only BCC recipients are set (no To or CC)
+    pub fn is_bcc_only(&self) -> bool {
+        let is_empty = |list: &Option<Vec<EmailAddress>>| list.as_ref().is_none_or(Vec::is_empty);
+        is_empty(&self.to) && is_empty(&self.cc) && !is_empty(&self.bcc)
+    }
+
     /// Create a new builder for Recipients
     pub fn builder() -> RecipientsBuilder {
         RecipientsBuilder::default()
@@ -305,42 +871,371 @@ pub struct RecipientsBuilder {
     to: Option<Vec<EmailAddress>>,
     cc: Option<Vec<EmailAddress>>,
     bcc: Option<Vec<EmailAddress>>,
+    skip_deduplication: bool,
+    case_insensitive_local_part: bool,
 }
 
 impl RecipientsBuilder {
     /// Set To recipients
     pub fn to(mut self, addresses: Vec<EmailAddress>) -> Self {
-        self.to = Some(Recipients::deduplicate(addresses));
+        self.to = Some(addresses);
         self
     }
 
     /// Set CC recipients
     pub fn cc(mut self, addresses: Vec<EmailAddress>) -> Self {
-        self.cc = Some(Recipients::deduplicate(addresses));
+        self.cc = Some(addresses);
         self
     }
 
     /// Set BCC recipients
     pub fn bcc(mut self, addresses: Vec<EmailAddress>) -> Self {
-        self.bcc = Some(Recipients::deduplicate(addresses));
+        self.bcc = Some(addresses);
+        self
+    }
+
+    /// Enable or disable deduplication (enabled by default)
+    ///
+    /// Disabling keeps duplicate addresses as given, so the same recipient may receive
+    /// multiple copies. Only disable it when duplicates are intentional.
+    pub fn deduplicate(mut self, enabled: bool) -> Self {
+        self.skip_deduplication = !enabled;
+        self
+    }
+
+    /// Treat the local part as case-insensitive when deduplicating (disabled by default)
+    ///
+    /// Domains are always compared case-insensitively. RFC 5321 lets a server treat
+    /// `User@example.com` and `user@example.com` as different mailboxes, so they are kept
+    /// apart by default; enable this to collapse them, as virtually all providers do.
+    pub fn case_insensitive_local_part(mut self, enabled: bool) -> Self {
+        self.case_insensitive_local_part = enabled;
         self
     }
 
     /// Build Recipients
+    ///
+    /// Empty lists are normalized to `None`.
     pub fn build(self) -> Recipients {
+        let dedup = |addresses: Option<Vec<EmailAddress>>| {
+            let addresses = addresses.filter(|addresses| !addresses.is_empty());
+            if self.skip_deduplication {
+                addresses
+            } else {
+                addresses.map(|addresses| {
+                    Recipients::deduplicate_with(addresses, self.case_insensitive_local_part)
+                })
+            }
+        };
+
         Recipients {
-            to: self.to,
-            cc: self.cc,
-            bcc: self.bcc,
+            to: dedup(self.to),
+            cc: dedup(self.cc),
+            bcc: dedup(self.bcc),
+        }
+    }
+}
+
+/// Problem found by `EmailClient::validate`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssue {
+    /// Address that is not a valid email address (see `EmailAddress::is_valid`)
+    InvalidAddress {
+        /// Field of the address (`sender`, `to`, `cc`, `bcc` or `replyTo`)
+        field: &'static str,
+        /// The invalid address
+        address: String,
+    },
+    /// No To, CC or BCC recipient
+    NoRecipients,
+    /// Empty subject
+    EmptySubject,
+    /// Neither `body_html` nor `body_text` is set
+    MissingBody,
+    /// Submission larger than `MAX_EMAIL_SIZE`
+    TooLarge {
+        /// Estimated submission size in bytes
+        size: usize,
+    },
+    /// More To, CC and BCC recipients than `MAX_RECIPIENTS`
+    TooManyRecipients {
+        /// Number of recipients
+        count: usize,
+    },
+    /// Sender compartment that is not an OCID
+    InvalidCompartment(String),
+    /// Sender that is not an `ACTIVE` approved sender in the compartment
+    SenderNotApproved {
+        /// Sender address
+        address: String,
+        /// Compartment that was checked
+        compartment_id: String,
+    },
+    /// Sender approval could not be checked (e.g., the list senders request failed)
+    SenderCheckFailed(String),
+}
+
+impl std::fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationIssue::InvalidAddress { field, address } => {
+                write!(f, "Invalid {} address: '{}'", field, address)
+            }
+            ValidationIssue::NoRecipients => write!(f, "At least one recipient is required"),
+            ValidationIssue::EmptySubject => write!(f, "Subject is empty"),
+            ValidationIssue::MissingBody => {
+                write!(f, "At least one of body_html or body_text is required")
+            }
+            ValidationIssue::TooLarge { size } => write!(
+                f,
+                "Email size ({} bytes) exceeds the maximum of {} bytes",
+                size, MAX_EMAIL_SIZE
+            ),
+            ValidationIssue::TooManyRecipients { count } => write!(
+                f,
+                "Too many recipients ({}), the maximum is {} per email \
+                 (split with Email::split_by_recipient_chunk)",
+                count, MAX_RECIPIENTS
+            ),
+            ValidationIssue::InvalidCompartment(id) => write!(
+                f,
+                "Invalid compartment OCID: '{}' (must start with 'ocid1.')",
+                id
+            ),
+            ValidationIssue::SenderNotApproved {
+                address,
+                compartment_id,
+            } => write!(
+                f,
+                "Sender '{}' is not an active approved sender in compartment '{}'",
+                crate::utils::mask_email(address),
+                crate::utils::mask_ocid(compartment_id)
+            ),
+            ValidationIssue::SenderCheckFailed(reason) => {
+                write!(f, "Sender approval could not be checked: {}", reason)
+            }
         }
     }
 }
 
+/// Maximum submission size in bytes (OCI Email Delivery default message size limit: 2 MB)
+///
+/// Reported by `Email::local_issues` as `ValidationIssue::TooLarge`, but not enforced when
+/// building or sending: the limit can be raised per tenancy.
+pub const MAX_EMAIL_SIZE: usize = 2 * 1024 * 1024;
+
+/// Maximum number of To, CC and BCC recipients per submission (OCI Email Delivery limit)
+///
+/// OCI rejects larger submissions with `400 Bad Request`; see `Email::split_by_recipient_chunk`.
+pub const MAX_RECIPIENTS: usize = 50;
+
+// `EmailAddress` equality ignores display names: compare them too
+impl PartialEq for Email {
+    fn eq(&self, other: &Self) -> bool {
+        self.message_id == other.message_id
+            && self.sender == other.sender
+            && self.recipients == other.recipients
+            && self.subject == other.subject
+            && self.body_html == other.body_html
+            && self.body_text == other.body_text
+            && self.reply_to == other.reply_to
+            && self.headers == other.headers
+            && self.address_names().eq(other.address_names())
+    }
+}
+
 impl Email {
     /// Create a new builder for Email
     pub fn builder() -> EmailBuilder {
         EmailBuilder::default()
     }
+
+    /// Display names of the sender, recipient and reply-to addresses, in order
+    fn address_names(&self) -> impl Iterator<Item = Option<&str>> {
+        std::iter::once(&self.sender.sender_address)
+            .chain(self.recipients.to.iter().flatten())
+            .chain(self.recipients.cc.iter().flatten())
+            .chain(self.recipients.bcc.iter().flatten())
+            .chain(self.reply_to.iter().flatten())
+            .map(|address| address.name.as_deref())
+    }
+
+    /// Estimated submission size in bytes
+    ///
+    /// Returns the length of the serialized JSON request body, without allocating it.
+    /// The compartment OCID is set on send, so an email built with an empty
+    /// compartment will grow by the OCID length (about 80 bytes).
+    pub fn estimated_size(&self) -> usize {
+        let mut counter = ByteCounter(0);
+        // Writing to ByteCounter never fails and Email always serializes
+        let _ = serde_json::to_writer(&mut counter, self);
+        counter.0
+    }
+
+    /// Copies of this email, each with at most `max` recipients, `max` clamped to `1..=MAX_RECIPIENTS`
+    ///
+    /// For `EmailClient::send_batch`. Recipients are taken in To, CC, BCC order and keep
+    /// their field, so that only the last chunk has fewer than `max` recipients. A `max`
+    /// of 0 splits into one recipient per chunk, and one above `MAX_RECIPIENTS` into
+    /// chunks of `MAX_RECIPIENTS`. Returns a single unchanged copy if the email has no
+    /// more than `max` recipients.
+    ///
+    /// Each chunk is sent as a separate message: recipients don't see the To and CC
+    /// recipients of other chunks. Since message IDs must be unique, a set `message_id`
+    /// is replaced by a new `<uuid@domain>` per chunk, keeping its domain (or cleared
+    /// so OCI generates one if it has no domain). Custom headers are copied as-is.
+    ///
+    /// # Example
+    /// ```
+    /// # use oci_api::email::{Email, EmailAddress, MAX_RECIPIENTS, Recipients};
+    /// let bcc = (0..120)
+    ///     .map(|i| EmailAddress::new(format!("user{}@example.com", i)))
+    ///     .collect();
+    /// let email = Email::builder()
+    ///     .sender(EmailAddress::new("news@example.com"))
+    ///     .recipients(Recipients::bcc(bcc))
+    ///     .subject("Newsletter")
+    ///     .body_text("Hello")
+    ///     .build()?;
+    ///
+    /// let chunks = email.split_by_recipient_chunk(MAX_RECIPIENTS);
+    /// assert_eq!(chunks.len(), 3);
+    /// # Ok::<(), oci_api::OciError>(())
+    /// ```
+    pub fn split_by_recipient_chunk(&self, max: usize) -> Vec<Email> {
+        let max = max.clamp(1, MAX_RECIPIENTS);
+        if self.recipients.count() <= max {
+            return vec![self.clone()];
+        }
+
+        let fields = [
+            &self.recipients.to,
+            &self.recipients.cc,
+            &self.recipients.bcc,
+        ];
+        let recipients: Vec<(usize, &EmailAddress)> = fields
+            .into_iter()
+            .enumerate()
+            .flat_map(|(field, addresses)| addresses.iter().flatten().map(move |a| (field, a)))
+            .collect();
+
+        recipients
+            .chunks(max)
+            .map(|chunk| {
+                let mut lists: [Vec<EmailAddress>; 3] = Default::default();
+                for (field, address) in chunk {
+                    lists[*field].push((*address).clone());
+                }
+                let [to, cc, bcc] = lists.map(|list| Some(list).filter(|list| !list.is_empty()));
+
+                Email {
+                    message_id: self.message_id.as_deref().and_then(chunk_message_id),
+                    recipients: Recipients { to, cc, bcc },
+                    ..self.clone()
+                }
+            })
+            .collect()
+    }
+
+    /// Email with international domains converted to ASCII (see `EmailAddress::ascii_email`)
+    ///
+    /// Applies to the sender, recipient and reply-to addresses; display names are kept.
+    /// Borrows `self` when every domain is already ASCII.
+    pub(crate) fn with_ascii_domains(&self) -> crate::error::Result<Cow<'_, Email>> {
+        let is_ascii = |address: &EmailAddress| address.domain().is_none_or(|d| d.is_ascii());
+        if self.addresses().all(is_ascii) {
+            return Ok(Cow::Borrowed(self));
+        }
+
+        let mut email = self.clone();
+        for address in email.addresses_mut() {
+            if !is_ascii(address) {
+                address.email = address.ascii_email()?.into_owned();
+            }
+        }
+        Ok(Cow::Owned(email))
+    }
+
+    /// Validation issues that don't need OCI (see `EmailClient::validate`)
+    pub(crate) fn local_issues(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        let fields = [
+            ("sender", std::slice::from_ref(&self.sender.sender_address)),
+            ("to", self.recipients.to.as_deref().unwrap_or_default()),
+            ("cc", self.recipients.cc.as_deref().unwrap_or_default()),
+            ("bcc", self.recipients.bcc.as_deref().unwrap_or_default()),
+            ("replyTo", self.reply_to.as_deref().unwrap_or_default()),
+        ];
+        for (field, addresses) in fields {
+            issues.extend(
+                addresses
+                    .iter()
+                    .filter(|address| !address.is_valid())
+                    .map(|address| ValidationIssue::InvalidAddress {
+                        field,
+                        address: address.email.clone(),
+                    }),
+            );
+        }
+
+        if fields[1..4]
+            .iter()
+            .all(|(_, addresses)| addresses.is_empty())
+        {
+            issues.push(ValidationIssue::NoRecipients);
+        }
+        if self.subject.trim().is_empty() {
+            issues.push(ValidationIssue::EmptySubject);
+        }
+        if self.body_html.is_none() && self.body_text.is_none() {
+            issues.push(ValidationIssue::MissingBody);
+        }
+
+        let size = self.estimated_size();
+        if size > MAX_EMAIL_SIZE {
+            issues.push(ValidationIssue::TooLarge { size });
+        }
+
+        let count = self.recipients.count();
+        if count > MAX_RECIPIENTS {
+            issues.push(ValidationIssue::TooManyRecipients { count });
+        }
+
+        issues
+    }
+
+    /// Sender, recipient and reply-to addresses
+    fn addresses(&self) -> impl Iterator<Item = &EmailAddress> {
+        std::iter::once(&self.sender.sender_address)
+            .chain(self.recipients.to.iter().flatten())
+            .chain(self.recipients.cc.iter().flatten())
+            .chain(self.recipients.bcc.iter().flatten())
+            .chain(self.reply_to.iter().flatten())
+    }
+
+    /// Sender, recipient and reply-to addresses (mutable)
+    fn addresses_mut(&mut self) -> impl Iterator<Item = &mut EmailAddress> {
+        std::iter::once(&mut self.sender.sender_address)
+            .chain(self.recipients.to.iter_mut().flatten())
+            .chain(self.recipients.cc.iter_mut().flatten())
+            .chain(self.recipients.bcc.iter_mut().flatten())
+            .chain(self.reply_to.iter_mut().flatten())
+    }
+}
+
+/// `io::Write` sink that only counts bytes
+struct ByteCounter(usize);
+
+impl std::io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
 }
 
 /// Builder for Email
@@ -354,6 +1249,10 @@ pub struct EmailBuilder {
     body_text: Option<String>,
     reply_to: Option<Vec<EmailAddress>>,
     headers: Option<std::collections::HashMap<String, String>>,
+    importance: Option<Importance>,
+    message_id_domain: Option<String>,
+    auto_text_from_html: bool,
+    list_unsubscribe: Vec<String>,
 }
 
 impl EmailBuilder {
@@ -387,6 +1286,15 @@ impl EmailBuilder {
         self
     }
 
+    /// Set HTML body rendered from a template (see `Template::render`)
+    pub fn body_html_template(
+        self,
+        template: &Template,
+        context: &HashMap<String, String>,
+    ) -> Self {
+        self.body_html(template.render(context))
+    }
+
     /// Set plain text body
     pub fn body_text(mut self, body_text: impl Into<String>) -> Self {
         self.body_text = Some(body_text.into());
@@ -394,17 +1302,62 @@ impl EmailBuilder {
     }
 
     /// Set reply-to addresses
+    ///
+    /// Duplicates are removed (like recipients) and each address is validated on `build`.
+    /// All addresses end up in one `Reply-To` header (see `Email::reply_to`).
     pub fn reply_to(mut self, reply_to: Vec<EmailAddress>) -> Self {
         self.reply_to = Some(reply_to);
         self
     }
 
+    /// Set a single reply-to address with a display name
+    pub fn reply_to_one(self, email: impl Into<String>, name: impl Into<String>) -> Self {
+        self.reply_to(vec![EmailAddress::with_name(email, name)])
+    }
+
     /// Set custom headers
     pub fn headers(mut self, headers: std::collections::HashMap<String, String>) -> Self {
         self.headers = Some(headers);
         self
     }
 
+    /// Set importance (overrides priority headers set via `headers`)
+    pub fn importance(mut self, importance: Importance) -> Self {
+        self.importance = Some(importance);
+        self
+    }
+
+    /// Derive a plain-text body from the HTML body when `body_text` is not set (disabled by default)
+    ///
+    /// Tags are stripped, block elements (paragraphs, line breaks, list items, ...) become
+    /// line breaks, `<script>`/`<style>` content is dropped and common entities are decoded.
+    /// This is a plain-text fallback for text-only clients, not a faithful rendering.
+    pub fn auto_text_from_html(mut self, enabled: bool) -> Self {
+        self.auto_text_from_html = enabled;
+        self
+    }
+
+    /// Generate the message ID as `<uuid@domain>` if not set via `message_id`
+    ///
+    /// Sets the `messageId` field (OCI's `Message-ID` header) instead of letting OCI
+    /// generate one, so the ID uses your sending domain and is known before sending.
+    /// A new ID is generated on each `build`: set `message_id` for an ID that stays the
+    /// same across rebuilds (e.g. for idempotent retries). The domain is validated on `build`.
+    pub fn auto_message_id(mut self, domain: impl Into<String>) -> Self {
+        self.message_id_domain = Some(domain.into());
+        self
+    }
+
+    /// Add a `List-Unsubscribe` target (`mailto:` address or `https:` URL)
+    ///
+    /// Gmail and Yahoo require one-click unsubscribe for bulk senders. An `https:` URL
+    /// also sets `List-Unsubscribe-Post: List-Unsubscribe=One-Click` (RFC 8058), so the
+    /// URL must accept that POST. Call twice to offer both forms. Validated on `build`.
+    pub fn list_unsubscribe(mut self, url_or_mailto: impl Into<String>) -> Self {
+        self.list_unsubscribe.push(url_or_mailto.into());
+        self
+    }
+
     /// Build Email
     ///
     /// Returns an error if required fields are missing or invalid
@@ -434,17 +1387,210 @@ impl EmailBuilder {
             ));
         }
 
-        Ok(Email {
-            message_id: self.message_id,
-            sender,
-            recipients,
-            subject,
-            body_html: self.body_html,
-            body_text: self.body_text,
-            reply_to: self.reply_to,
-            headers: self.headers,
-        })
-    }
+        // Merge importance headers into custom headers
+        let mut headers = self.headers;
+        if let Some(importance) = self.importance {
+            let map = headers.get_or_insert_with(Default::default);
+            for (name, value) in importance.headers() {
+                map.insert(name.to_string(), value.to_string());
+            }
+        }
+
+        // Generated message ID
+        let mut message_id = self.message_id;
+        if let Some(domain) = &self.message_id_domain {
+            if !is_valid_domain(domain) {
+                return Err(crate::error::OciError::ConfigError(format!(
+                    "Invalid Message-ID domain: '{}'",
+                    domain
+                )));
+            }
+
+            message_id.get_or_insert_with(|| format!("<{}@{}>", random_uuid(), domain));
+        }
+
+        // List-Unsubscribe headers
+        if !self.list_unsubscribe.is_empty() {
+            let targets = self
+                .list_unsubscribe
+                .iter()
+                .map(|target| list_unsubscribe_target(target))
+                .collect::<crate::error::Result<Vec<_>>>()?;
+
+            let map = headers.get_or_insert_with(Default::default);
+            if targets.iter().any(|target| target.starts_with("<https:")) {
+                map.insert(
+                    "List-Unsubscribe-Post".to_string(),
+                    "List-Unsubscribe=One-Click".to_string(),
+                );
+            }
+            map.insert("List-Unsubscribe".to_string(), targets.join(", "));
+        }
+
+        // Plain-text fallback derived from HTML
+        let body_text = match (&self.body_text, &self.body_html) {
+            (None, Some(html)) if self.auto_text_from_html => Some(html_to_text(html)),
+            _ => self.body_text,
+        };
+
+        // Deduplicated, validated reply-to addresses
+        let reply_to = self
+            .reply_to
+            .map(Recipients::deduplicate)
+            .filter(|reply_to| !reply_to.is_empty());
+        if let Some(invalid) = reply_to.iter().flatten().find(|addr| !addr.is_valid()) {
+            return Err(crate::error::OciError::ConfigError(format!(
+                "Invalid reply-to address: '{}'",
+                invalid.email
+            )));
+        }
+
+        let email = Email {
+            message_id,
+            sender,
+            recipients,
+            subject,
+            body_html: self.body_html,
+            body_text,
+            reply_to,
+            headers,
+        };
+
+        // Size is not checked: the limit can be raised per tenancy (see `local_issues`)
+        Ok(email)
+    }
+}
+
+/// Whether a recipient list is absent or empty (not serialized: see `Recipients`)
+fn is_none_or_empty(addresses: &Option<Vec<EmailAddress>>) -> bool {
+    addresses.as_ref().is_none_or(Vec::is_empty)
+}
+
+/// `List-Unsubscribe` entry (`<target>`) for a `mailto:` address or `https:` URL
+fn list_unsubscribe_target(target: &str) -> crate::error::Result<String> {
+    let invalid = || {
+        crate::error::OciError::ConfigError(format!(
+            "Invalid List-Unsubscribe target (expected mailto: or https: URL): '{}'",
+            target
+        ))
+    };
+
+    let url = reqwest::Url::parse(target.trim()).map_err(|_| invalid())?;
+    let valid = match url.scheme() {
+        "mailto" => EmailAddress::new(url.path()).is_valid(),
+        "https" => url.host_str().is_some_and(|host| !host.is_empty()),
+        _ => false,
+    };
+    if !valid {
+        return Err(invalid());
+    }
+
+    Ok(format!("<{}>", url))
+}
+
+/// Plain text from HTML: strip tags, break lines at block elements, decode common entities
+fn html_to_text(html: &str) -> String {
+    const BLOCK_TAGS: [&str; 14] = [
+        "br", "p", "div", "li", "tr", "h1", "h2", "h3", "h4", "h5", "h6", "table", "ul", "ol",
+    ];
+
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            rest = &rest[start..];
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let name: String = tag
+            .trim_start_matches('/')
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+
+        // Drop script/style content
+        if (name == "script" || name == "style") && !tag.starts_with('/') {
+            let close = format!("</{}", name);
+            match rest.to_ascii_lowercase().find(&close) {
+                Some(i) => {
+                    rest = &rest[i..];
+                    if let Some(end) = rest.find('>') {
+                        rest = &rest[end + 1..];
+                    }
+                }
+                None => rest = "",
+            }
+            continue;
+        }
+
+        if BLOCK_TAGS.contains(&name.as_str()) {
+            text.push('\n');
+            if name == "li" && !tag.starts_with('/') {
+                text.push_str("- ");
+            }
+        }
+    }
+    text.push_str(rest);
+
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+
+    // Collapse whitespace within lines and drop blank lines
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty() && line != "-")
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Check that `domain` is a valid DNS host name (e.g., `mail.example.com`)
+fn is_valid_domain(domain: &str) -> bool {
+    !domain.is_empty()
+        && domain.len() <= 253
+        && domain.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+/// New `<uuid@domain>` message ID with the domain of `message_id`, if it has one
+fn chunk_message_id(message_id: &str) -> Option<String> {
+    let id = message_id
+        .trim()
+        .trim_start_matches('<')
+        .trim_end_matches('>');
+    let (_, domain) = id.rsplit_once('@')?;
+    Some(format!("<{}@{}>", random_uuid(), domain)).filter(|_| !domain.is_empty())
+}
+
+/// Random (version 4) UUID string
+fn random_uuid() -> String {
+    let mut bytes: [u8; 16] = rand::random();
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
 }
 
 #[cfg(test)]
@@ -489,6 +1635,46 @@ mod tests {
         assert_eq!(recipients.bcc.as_ref().unwrap().len(), 1);
     }
 
+    #[test]
+    fn test_recipients_empty_lists_not_serialized() {
+        let recipients = Recipients {
+            to: Some(vec![]),
+            cc: Some(vec![EmailAddress::new("cc@example.com")]),
+            bcc: Some(vec![]),
+        };
+        let json = serde_json::to_value(&recipients).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"cc": [{"email": "cc@example.com"}]})
+        );
+
+        let recipients = Recipients::builder()
+            .to(vec![])
+            .cc(vec![EmailAddress::new("cc@example.com")])
+            .bcc(vec![])
+            .build();
+        assert_eq!(recipients.to, None);
+        assert_eq!(recipients.bcc, None);
+
+        // Still reported when no list has a recipient
+        let email = Email::builder()
+            .sender(EmailAddress::new("sender@example.com"))
+            .recipients(Recipients {
+                to: Some(vec![]),
+                cc: None,
+                bcc: None,
+            })
+            .subject("Test")
+            .body_text("Test")
+            .build()
+            .unwrap();
+        assert!(
+            email
+                .local_issues()
+                .contains(&ValidationIssue::NoRecipients)
+        );
+    }
+
     #[test]
     fn test_submit_email_request_serialization() {
         let mut request = Email {
@@ -511,6 +1697,143 @@ mod tests {
         assert!(json.contains("\"messageId\""));
     }
 
+    #[test]
+    fn test_email_serde_round_trip() {
+        let mut email = Email {
+            message_id: Some("<id-1@example.com>".to_string()),
+            sender: Sender::with_name("sender@example.com", "Sender"),
+            recipients: Recipients {
+                to: Some(vec![EmailAddress::with_name("to@example.com", "To")]),
+                cc: Some(vec![EmailAddress::new("cc@example.com")]),
+                bcc: Some(vec![EmailAddress::new("bcc@example.com")]),
+            },
+            subject: "Subject".to_string(),
+            body_html: Some("<p>Hello</p>".to_string()),
+            body_text: Some("Hello".to_string()),
+            reply_to: Some(vec![EmailAddress::with_name("reply@example.com", "Reply")]),
+            headers: Some(HashMap::from([
+                ("X-Campaign".to_string(), "spring".to_string()),
+                ("X-Priority".to_string(), "1".to_string()),
+            ])),
+        };
+        email.sender.set_compartment_id("ocid1.compartment.test");
+
+        let json = serde_json::to_string(&email).unwrap();
+        let decoded: Email = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, email);
+
+        // Display names are compared too
+        let mut renamed = email.clone();
+        renamed.recipients.to = Some(vec![EmailAddress::with_name("to@example.com", "Other")]);
+        assert_ne!(renamed, email);
+        let mut renamed = email.clone();
+        renamed.sender.sender_address.name = None;
+        assert_ne!(renamed, email);
+
+        // Minimal email (optional fields omitted)
+        let minimal = Email {
+            message_id: None,
+            body_html: None,
+            reply_to: None,
+            headers: None,
+            recipients: Recipients::bcc(vec![EmailAddress::new("bcc@example.com")]),
+            ..email.clone()
+        };
+        let decoded: Email =
+            serde_json::from_str(&serde_json::to_string(&minimal).unwrap()).unwrap();
+        assert_eq!(decoded, minimal);
+        assert_ne!(decoded, email);
+    }
+
+    #[test]
+    fn test_reply_to_deduplicated_and_validated() {
+        let builder = || {
+            Email::builder()
+                .sender(EmailAddress::new("sender@example.com"))
+                .recipients(Recipients::to(vec![EmailAddress::new("to@example.com")]))
+                .subject("Hello")
+                .body_text("Hello")
+        };
+
+        let email = builder()
+            .reply_to(vec![
+                EmailAddress::with_name("support@example.com", "Support"),
+                EmailAddress::new("sales@example.com"),
+                EmailAddress::new("support@example.com"),
+            ])
+            .build()
+            .unwrap();
+        let reply_to = email.reply_to.unwrap();
+        assert_eq!(reply_to.len(), 2);
+        assert_eq!(reply_to[0].email, "support@example.com");
+        assert_eq!(reply_to[0].name.as_deref(), Some("Support"));
+        assert_eq!(reply_to[1].email, "sales@example.com");
+
+        // An empty list is omitted
+        let email = builder().reply_to(Vec::new()).build().unwrap();
+        assert!(email.reply_to.is_none());
+
+        let result = builder()
+            .reply_to(vec![
+                EmailAddress::new("support@example.com"),
+                EmailAddress::new("not-an-address"),
+            ])
+            .build();
+        match result.unwrap_err() {
+            crate::error::OciError::ConfigError(msg) => {
+                assert_eq!(msg, "Invalid reply-to address: 'not-an-address'")
+            }
+            e => panic!("Expected ConfigError, got: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_reply_to_one() {
+        let email = Email::builder()
+            .sender(EmailAddress::new("sender@example.com"))
+            .recipients(Recipients::to(vec![EmailAddress::new("to@example.com")]))
+            .subject("Hello")
+            .body_text("Hello")
+            .reply_to_one("support@example.com", "Support Team")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            email.reply_to,
+            Some(vec![EmailAddress::new("support@example.com")])
+        );
+        let json = serde_json::to_value(&email).unwrap();
+        assert_eq!(
+            json["replyTo"],
+            serde_json::json!([{"email": "support@example.com", "name": "Support Team"}])
+        );
+    }
+
+    #[test]
+    fn test_email_eq_ignores_header_order() {
+        let email = |headers: &[(&str, &str)]| Email {
+            message_id: None,
+            sender: Sender::new("sender@example.com"),
+            recipients: Recipients::to(vec![EmailAddress::new("to@example.com")]),
+            subject: "Subject".to_string(),
+            body_html: None,
+            body_text: Some("Hello".to_string()),
+            reply_to: None,
+            headers: Some(
+                headers
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            ),
+        };
+
+        assert_eq!(
+            email(&[("X-A", "1"), ("X-B", "2")]),
+            email(&[("X-B", "2"), ("X-A", "1")])
+        );
+        assert_ne!(email(&[("X-A", "1")]), email(&[("X-A", "2")]));
+    }
+
     #[test]
     fn test_submit_email_request_builder() {
         let mut request = Email::builder()
@@ -588,6 +1911,128 @@ mod tests {
         assert_eq!(response.envelope_id, "env-456");
     }
 
+    #[test]
+    fn test_submit_email_response_without_suppressions() {
+        let json = r#"{"messageId": "msg-1", "envelopeId": "env-1"}"#;
+        let response: SubmitEmailResponse = serde_json::from_str(json).unwrap();
+
+        assert_eq!(response.suppressed_count(), 0);
+        assert!(response.was_fully_delivered());
+
+        let recipients = Recipients::to(vec![EmailAddress::new("a@example.com")])
+            .add_cc(vec![EmailAddress::new("b@example.com")]);
+        assert_eq!(response.delivered_recipients(&recipients).len(), 2);
+    }
+
+    #[test]
+    fn test_submit_email_response_audit_fields() {
+        let json = r#"{
+            "messageId": "msg-123",
+            "envelopeId": "env-456",
+            "timeSubmitted": "2024-05-01T12:00:00.000Z",
+            "region": "ap-seoul-1"
+        }"#;
+        let response: SubmitEmailResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.extra.len(), 2);
+        assert_eq!(response.extra["timeSubmitted"], "2024-05-01T12:00:00.000Z");
+        assert_eq!(response.extra["region"], "ap-seoul-1");
+
+        // Unknown fields survive a round trip
+        let value = serde_json::to_value(&response).unwrap();
+        assert_eq!(value["region"], "ap-seoul-1");
+        assert_eq!(value["timeSubmitted"], "2024-05-01T12:00:00.000Z");
+
+        let json = r#"{"messageId": "msg-123", "envelopeId": "env-456"}"#;
+        let response: SubmitEmailResponse = serde_json::from_str(json).unwrap();
+        assert!(response.extra.is_empty());
+
+        let value = serde_json::to_value(&response).unwrap();
+        assert_eq!(value.as_object().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_submit_email_response_with_suppressions() {
+        let json = r#"{
+            "messageId": "msg-1",
+            "envelopeId": "env-1",
+            "suppressedRecipients": [{"email": "bounced@Example.com"}]
+        }"#;
+        let response: SubmitEmailResponse = serde_json::from_str(json).unwrap();
+
+        assert_eq!(response.suppressed_count(), 1);
+        assert!(!response.was_fully_delivered());
+
+        let recipients = Recipients::builder()
+            .to(vec![EmailAddress::new("a@example.com")])
+            .cc(vec![EmailAddress::new("bounced@example.com")])
+            .bcc(vec![EmailAddress::new("c@example.com")])
+            .build();
+        let delivered = response.delivered_recipients(&recipients);
+        assert_eq!(
+            delivered,
+            vec![
+                EmailAddress::new("a@example.com"),
+                EmailAddress::new("c@example.com")
+            ]
+        );
+
+        // Like `EmailAddress` equality, the local part is case-sensitive
+        let recipients = Recipients::to(vec![EmailAddress::new("Bounced@example.com")]);
+        assert_eq!(response.delivered_recipients(&recipients).len(), 1);
+    }
+
+    fn submit_response(suppressed: &[&str]) -> SubmitEmailResponse {
+        SubmitEmailResponse {
+            message_id: "msg-1".to_string(),
+            envelope_id: "env-1".to_string(),
+            suppressed_recipients: Some(suppressed.iter().map(|e| EmailAddress::new(*e)).collect()),
+            extra: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_delivery_outcome_all_accepted() {
+        let recipients = Recipients::to(vec![
+            EmailAddress::new("a@example.com"),
+            EmailAddress::new("b@example.com"),
+        ]);
+        let outcome = DeliveryOutcome::from_response(submit_response(&[]), &recipients);
+
+        assert_eq!(outcome.accepted.len(), 2);
+        assert!(outcome.suppressed.is_empty());
+        assert!(outcome.is_fully_accepted());
+        assert!(!outcome.is_partial());
+        assert_eq!(outcome.message_id, "msg-1");
+        assert_eq!(outcome.envelope_id, "env-1");
+    }
+
+    #[test]
+    fn test_delivery_outcome_all_suppressed() {
+        let recipients = Recipients::to(vec![EmailAddress::new("a@example.com")])
+            .add_bcc(vec![EmailAddress::new("b@example.com")]);
+        let outcome = DeliveryOutcome::from_response(
+            submit_response(&["a@example.com", "b@example.com"]),
+            &recipients,
+        );
+
+        assert!(outcome.accepted.is_empty());
+        assert_eq!(outcome.suppressed.len(), 2);
+        assert!(!outcome.is_fully_accepted());
+        assert!(!outcome.is_partial());
+    }
+
+    #[test]
+    fn test_delivery_outcome_mixed() {
+        let recipients = Recipients::to(vec![EmailAddress::new("a@example.com")])
+            .add_cc(vec![EmailAddress::new("b@example.com")]);
+        let outcome =
+            DeliveryOutcome::from_response(submit_response(&["b@example.com"]), &recipients);
+
+        assert_eq!(outcome.accepted, vec![EmailAddress::new("a@example.com")]);
+        assert_eq!(outcome.suppressed, vec![EmailAddress::new("b@example.com")]);
+        assert!(outcome.is_partial());
+    }
+
     #[test]
     fn test_complete_email_request_with_all_fields() {
         use std::collections::HashMap;
@@ -788,6 +2233,55 @@ mod tests {
         assert_eq!(recipients.bcc.as_ref().unwrap().len(), 1);
     }
 
+    #[test]
+    fn test_mixed_case_domain_deduplication() {
+        let recipients = Recipients::to(vec![
+            EmailAddress::new("User@Example.com"),
+            EmailAddress::new("User@example.COM"),
+            EmailAddress::new("user@example.com"),
+        ]);
+
+        // Domain case is ignored, local part case is kept; the first spelling wins
+        let to = recipients.to.unwrap();
+        assert_eq!(to.len(), 2);
+        assert_eq!(to[0].email, "User@Example.com");
+        assert_eq!(to[1].email, "user@example.com");
+
+        assert_eq!(
+            EmailAddress::new("a@Example.COM"),
+            EmailAddress::new("a@example.com")
+        );
+        assert_ne!(
+            EmailAddress::new("A@example.com"),
+            EmailAddress::new("a@example.com")
+        );
+        assert_eq!(
+            EmailAddress::new("User@Example.com").normalized(),
+            "User@example.com"
+        );
+    }
+
+    #[test]
+    fn test_case_insensitive_local_part_deduplication() {
+        let recipients = Recipients::builder()
+            .to(vec![
+                EmailAddress::new("User@Example.com"),
+                EmailAddress::new("user@example.com"),
+            ])
+            .cc(vec![
+                EmailAddress::new("A@b.com"),
+                EmailAddress::new("a@B.com"),
+            ])
+            .case_insensitive_local_part(true)
+            .build();
+
+        assert_eq!(
+            recipients.to.unwrap(),
+            [EmailAddress::new("User@Example.com")]
+        );
+        assert_eq!(recipients.cc.unwrap().len(), 1);
+    }
+
     #[test]
     fn test_recipients_add_methods_deduplication() {
         // Test adding duplicates
@@ -836,4 +2330,749 @@ mod tests {
         // Should keep only one (the first one encountered)
         assert_eq!(recipients.to.as_ref().unwrap().len(), 1);
     }
+
+    #[test]
+    fn test_importance_headers() {
+        let cases = [
+            (Importance::High, "1 (Highest)", "High"),
+            (Importance::Normal, "3 (Normal)", "Normal"),
+            (Importance::Low, "5 (Lowest)", "Low"),
+        ];
+
+        for (importance, x_priority, level) in cases {
+            let email = Email::builder()
+                .sender(EmailAddress::new("sender@example.com"))
+                .recipients(Recipients::to(vec![EmailAddress::new("to@example.com")]))
+                .subject("Test")
+                .body_text("Test")
+                .importance(importance)
+                .build()
+                .unwrap();
+
+            let headers = email.headers.unwrap();
+            assert_eq!(headers.len(), 3);
+            assert_eq!(headers["X-Priority"], x_priority);
+            assert_eq!(headers["Importance"], level);
+            assert_eq!(headers["X-MSMail-Priority"], level);
+        }
+    }
+
+    #[test]
+    fn test_importance_merges_with_custom_headers() {
+        use std::collections::HashMap;
+
+        let mut headers = HashMap::new();
+        headers.insert("X-Test".to_string(), "test-value".to_string());
+        headers.insert("X-Priority".to_string(), "wrong".to_string());
+
+        // importance is applied at build time, regardless of setter order
+        let email = Email::builder()
+            .sender(EmailAddress::new("sender@example.com"))
+            .recipients(Recipients::to(vec![EmailAddress::new("to@example.com")]))
+            .subject("Test")
+            .body_text("Test")
+            .importance(Importance::High)
+            .headers(headers)
+            .build()
+            .unwrap();
+
+        let headers = email.headers.unwrap();
+        assert_eq!(headers["X-Test"], "test-value");
+        assert_eq!(headers["X-Priority"], "1 (Highest)");
+    }
+
+    #[test]
+    fn test_email_address_parts() {
+        let addr = EmailAddress::new("user.name@Example.com");
+        assert_eq!(addr.local_part(), Some("user.name"));
+        assert_eq!(addr.domain(), Some("Example.com"));
+
+        let no_at = EmailAddress::new("not-an-address");
+        assert_eq!(no_at.local_part(), None);
+        assert_eq!(no_at.domain(), None);
+
+        let empty_domain = EmailAddress::new("user@");
+        assert_eq!(empty_domain.local_part(), Some("user"));
+        assert_eq!(empty_domain.domain(), None);
+    }
+
+    #[test]
+    fn test_email_address_is_valid() {
+        for valid in [
+            "user@example.com",
+            "first.last+tag@mail.example.co.kr",
+            "user@müller.de",
+        ] {
+            assert!(EmailAddress::new(valid).is_valid(), "{valid}");
+        }
+        for invalid in [
+            "",
+            "user",
+            "@example.com",
+            "user@",
+            "user@localhost",
+            "us er@example.com",
+            "user@exa_mple.com",
+            "user@-example.com",
+        ] {
+            assert!(!EmailAddress::new(invalid).is_valid(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_ascii_email() {
+        let idn = EmailAddress::with_name("user@müller.de", "Müller");
+        assert_eq!(idn.ascii_email().unwrap(), "user@xn--mller-kva.de");
+
+        let ascii = EmailAddress::new("user@example.com");
+        assert!(matches!(
+            ascii.ascii_email().unwrap(),
+            Cow::Borrowed("user@example.com")
+        ));
+
+        // Only the domain is converted
+        let unicode_local = EmailAddress::new("müller@example.com");
+        assert_eq!(unicode_local.ascii_email().unwrap(), "müller@example.com");
+
+        let invalid = EmailAddress::new("user@xn--ü.de");
+        assert!(invalid.ascii_email().is_err());
+    }
+
+    #[test]
+    fn test_email_with_ascii_domains() {
+        let email = Email::builder()
+            .sender(EmailAddress::new("noreply@bücher.example"))
+            .recipients(
+                Recipients::builder()
+                    .to(vec![EmailAddress::with_name("user@müller.de", "Müller")])
+                    .cc(vec![EmailAddress::new("cc@example.com")])
+                    .build(),
+            )
+            .reply_to(vec![EmailAddress::new("support@bücher.example")])
+            .subject("Hello")
+            .body_text("Hello")
+            .build()
+            .unwrap();
+
+        let converted = email.with_ascii_domains().unwrap();
+        assert!(matches!(converted, Cow::Owned(_)));
+        assert_eq!(
+            converted.sender.sender_address.email,
+            "noreply@xn--bcher-kva.example"
+        );
+        let to = &converted.recipients.to.as_ref().unwrap()[0];
+        assert_eq!(to.email, "user@xn--mller-kva.de");
+        assert_eq!(to.name.as_deref(), Some("Müller"));
+        assert_eq!(
+            converted.recipients.cc.as_ref().unwrap()[0].email,
+            "cc@example.com"
+        );
+        assert_eq!(
+            converted.reply_to.as_ref().unwrap()[0].email,
+            "support@xn--bcher-kva.example"
+        );
+
+        // The original is untouched, and ASCII-only emails are borrowed
+        assert_eq!(email.sender.sender_address.email, "noreply@bücher.example");
+        let mut ascii = email.clone();
+        ascii.sender.sender_address = EmailAddress::new("noreply@example.com");
+        ascii.recipients = Recipients::to(vec![EmailAddress::new("to@example.com")]);
+        ascii.reply_to = None;
+        assert!(matches!(
+            ascii.with_ascii_domains().unwrap(),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn test_group_by_domain() {
+        let addresses = vec![
+            EmailAddress::new("a@example.com"),
+            EmailAddress::new("b@other.org"),
+            EmailAddress::new("c@EXAMPLE.com"),
+            EmailAddress::new("no-domain"),
+        ];
+
+        let groups = EmailAddress::group_by_domain(&addresses);
+        assert_eq!(groups.len(), 3);
+
+        let example: Vec<_> = groups["example.com"].iter().map(|a| &a.email).collect();
+        assert_eq!(example, vec!["a@example.com", "c@EXAMPLE.com"]);
+        assert_eq!(groups["other.org"].len(), 1);
+        assert_eq!(groups[""][0].email, "no-domain");
+    }
+
+    #[test]
+    fn test_recipients_without_deduplication() {
+        let recipients = Recipients::to_raw(vec![
+            EmailAddress::new("user@example.com"),
+            EmailAddress::new("user@example.com"),
+        ]);
+        assert_eq!(recipients.to.as_ref().unwrap().len(), 2);
+
+        let recipients = Recipients::builder()
+            .bcc(vec![
+                EmailAddress::new("bcc@example.com"),
+                EmailAddress::new("bcc@example.com"),
+            ])
+            .deduplicate(false)
+            .build();
+        assert_eq!(recipients.bcc.as_ref().unwrap().len(), 2);
+
+        // Default behavior still deduplicates
+        let recipients = Recipients::builder()
+            .bcc(vec![
+                EmailAddress::new("bcc@example.com"),
+                EmailAddress::new("bcc@example.com"),
+            ])
+            .build();
+        assert_eq!(recipients.bcc.as_ref().unwrap().len(), 1);
+    }
+
+    fn sender(email: &str, state: SenderLifecycleState, is_spf: Option<bool>) -> SenderSummary {
+        SenderSummary {
+            id: format!("ocid1.emailsender.oc1..{}", email),
+            email_address: email.to_string(),
+            lifecycle_state: state,
+            time_created: "2024-01-01T00:00:00.000Z".to_string(),
+            is_spf,
+            compartment_id: None,
+            etag: None,
+        }
+    }
+
+    #[test]
+    fn test_sender_from_summary() {
+        let mut summary = sender("noreply@example.com", SenderLifecycleState::Active, None);
+
+        // Compartment left to EmailClient when the summary doesn't include it
+        let from_list = Sender::from_summary(&summary);
+        assert_eq!(from_list.sender_address.email, "noreply@example.com");
+        assert_eq!(from_list.sender_address.name, None);
+        assert!(from_list.compartment_id.is_empty());
+
+        summary.compartment_id = Some("ocid1.compartment.oc1..senders".to_string());
+        let named = Sender::from_summary_with_name(&summary, "Example");
+        assert_eq!(named.sender_address.email, "noreply@example.com");
+        assert_eq!(named.sender_address.name.as_deref(), Some("Example"));
+        assert_eq!(named.compartment_id, "ocid1.compartment.oc1..senders");
+
+        let mut email = Email::builder()
+            .sender(EmailAddress::from(&summary))
+            .recipients(Recipients::to(vec![EmailAddress::new("to@example.com")]))
+            .subject("Hello")
+            .body_text("Hello")
+            .build()
+            .unwrap();
+        assert_eq!(email.sender.sender_address.email, "noreply@example.com");
+
+        email.sender = Sender::from_summary(&summary);
+        let json = serde_json::to_value(&email).unwrap();
+        assert_eq!(
+            json["sender"]["senderAddress"]["email"],
+            "noreply@example.com"
+        );
+        assert_eq!(
+            json["sender"]["compartmentId"],
+            "ocid1.compartment.oc1..senders"
+        );
+    }
+
+    #[test]
+    fn test_sender_filter() {
+        use SenderLifecycleState::*;
+        let senders = [
+            sender("a@example.com", Active, Some(true)),
+            sender("b@Example.COM", Active, Some(true)),
+            sender("c@example.com", Active, Some(false)),
+            sender("d@example.com", Active, None),
+            sender("e@example.com", Creating, Some(true)),
+            sender("f@other.com", Active, Some(true)),
+        ];
+        let matching = |filter: &SenderFilter| {
+            senders
+                .iter()
+                .filter(|s| filter.matches(s))
+                .map(|s| s.email_address.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(matching(&SenderFilter::new()).len(), senders.len());
+        assert_eq!(
+            matching(
+                &SenderFilter::new()
+                    .state(Active)
+                    .spf_required(true)
+                    .domain("example.com")
+            ),
+            ["a@example.com", "b@Example.COM"]
+        );
+        assert_eq!(
+            matching(&SenderFilter::new().state(Active).spf_required(true)),
+            ["a@example.com", "b@Example.COM", "f@other.com"]
+        );
+        assert_eq!(
+            matching(&SenderFilter::new().state(Creating).domain("EXAMPLE.com")),
+            ["e@example.com"]
+        );
+        assert!(matching(&SenderFilter::new().domain("ample.com")).is_empty());
+    }
+
+    #[test]
+    fn test_sender_lifecycle_state_display() {
+        assert_eq!(SenderLifecycleState::Active.to_string(), "ACTIVE");
+        assert_eq!(
+            SenderLifecycleState::NeedsAttention.to_string(),
+            "NEEDS_ATTENTION"
+        );
+    }
+
+    #[test]
+    fn test_sender_lifecycle_state_round_trip() {
+        for state in SenderLifecycleState::ALL {
+            let parsed: SenderLifecycleState = state.to_string().parse().unwrap();
+            assert_eq!(parsed, state);
+
+            // Display form matches the serde wire form
+            let json = serde_json::to_string(&state).unwrap();
+            assert_eq!(json, format!("\"{}\"", state));
+        }
+
+        assert!("active".parse::<SenderLifecycleState>().is_err());
+        assert!("UNKNOWN".parse::<SenderLifecycleState>().is_err());
+    }
+
+    #[test]
+    fn test_sender_lifecycle_state_unknown() {
+        let state: SenderLifecycleState = serde_json::from_str("\"SUSPENDED_FOR_REVIEW\"").unwrap();
+        assert_eq!(
+            state,
+            SenderLifecycleState::Unknown("SUSPENDED_FOR_REVIEW".to_string())
+        );
+        assert!(!state.is_active());
+        assert!(!state.is_terminal());
+        assert_eq!(state.to_string(), "SUSPENDED_FOR_REVIEW");
+        assert_eq!(
+            serde_json::to_string(&state).unwrap(),
+            "\"SUSPENDED_FOR_REVIEW\""
+        );
+
+        // A sender list with an unknown state still deserializes
+        let senders: Vec<SenderSummary> = serde_json::from_value(serde_json::json!([
+            {
+                "id": "ocid1.emailsender.oc1..a",
+                "emailAddress": "a@example.com",
+                "lifecycleState": "ACTIVE",
+                "timeCreated": "2024-01-01T00:00:00Z"
+            },
+            {
+                "id": "ocid1.emailsender.oc1..b",
+                "emailAddress": "b@example.com",
+                "lifecycleState": "SUSPENDED_FOR_REVIEW",
+                "timeCreated": "2024-01-01T00:00:00Z"
+            }
+        ]))
+        .unwrap();
+        assert!(senders[0].lifecycle_state.is_active());
+        assert!(matches!(
+            senders[1].lifecycle_state,
+            SenderLifecycleState::Unknown(_)
+        ));
+
+        assert!(SenderLifecycleState::Active.is_active());
+        assert!(SenderLifecycleState::Deleted.is_terminal());
+        assert!(SenderLifecycleState::Failed.is_terminal());
+        assert!(!SenderLifecycleState::Deleting.is_terminal());
+    }
+
+    #[test]
+    fn test_estimated_size_matches_serialized_length() {
+        let mut headers = HashMap::new();
+        headers.insert("X-Campaign".to_string(), "spring \"sale\" ☀".to_string());
+
+        let email = Email::builder()
+            .sender(EmailAddress::with_name("sender@example.com", "보내는 사람"))
+            .recipients(Recipients::to(vec![
+                EmailAddress::new("user1@example.com"),
+                EmailAddress::with_name("user2@example.com", "User <2>"),
+            ]))
+            .subject("Size test")
+            .body_html("<p>Hello\nWorld</p>")
+            .body_text("Hello\tWorld")
+            .headers(headers)
+            .build()
+            .unwrap();
+
+        let actual = serde_json::to_string(&email).unwrap().len();
+        assert_eq!(email.estimated_size(), actual);
+    }
+
+    #[test]
+    fn test_build_accepts_oversized_email() {
+        let email = Email::builder()
+            .sender(EmailAddress::new("sender@example.com"))
+            .recipients(Recipients::to(vec![EmailAddress::new("to@example.com")]))
+            .subject("Too large")
+            .body_text("a".repeat(MAX_EMAIL_SIZE))
+            .build()
+            .unwrap();
+
+        // Reported, not rejected: the limit can be raised per tenancy
+        let size = email.estimated_size();
+        assert!(size > MAX_EMAIL_SIZE);
+        assert!(
+            email
+                .local_issues()
+                .contains(&ValidationIssue::TooLarge { size })
+        );
+    }
+
+    #[test]
+    fn test_bcc_only_serialization() {
+        let builder = || {
+            Email::builder()
+                .sender(EmailAddress::new("sender@example.com"))
+                .recipients(Recipients::bcc(vec![EmailAddress::new(
+                    "hidden@example.com",
+                )]))
+                .subject("Newsletter")
+                .body_text("Hello")
+        };
+
+        // Passes through as-is, without a To header
+        let email = builder().build().unwrap();
+        assert!(email.recipients.is_bcc_only());
+        let json = serde_json::to_value(&email).unwrap();
+        assert!(json["recipients"].get("to").is_none());
+        assert_eq!(json["recipients"]["bcc"][0]["email"], "hidden@example.com");
+        assert!(json.get("headerFields").is_none());
+    }
+
+    #[test]
+    fn test_auto_message_id() {
+        let email = Email::builder()
+            .sender(EmailAddress::new("sender@example.com"))
+            .recipients(Recipients::to(vec![EmailAddress::new("to@example.com")]))
+            .subject("Test")
+            .body_text("Test")
+            .auto_message_id("mail.example.com")
+            .build()
+            .unwrap();
+
+        assert!(email.headers.is_none());
+        let value = email.message_id.unwrap();
+        let id = value
+            .strip_prefix('<')
+            .and_then(|v| v.strip_suffix("@mail.example.com>"))
+            .unwrap();
+        let parts: Vec<&str> = id.split('-').collect();
+        assert_eq!(
+            parts.iter().map(|p| p.len()).collect::<Vec<_>>(),
+            [8, 4, 4, 4, 12]
+        );
+        assert!(parts[2].starts_with('4'));
+        assert!(id.chars().all(|c| c == '-' || c.is_ascii_hexdigit()));
+
+        // Explicit message ID is kept
+        let email = Email::builder()
+            .sender(EmailAddress::new("sender@example.com"))
+            .recipients(Recipients::to(vec![EmailAddress::new("to@example.com")]))
+            .subject("Test")
+            .body_text("Test")
+            .message_id("<custom@example.com>")
+            .auto_message_id("example.com")
+            .build()
+            .unwrap();
+        assert_eq!(email.message_id.as_deref(), Some("<custom@example.com>"));
+
+        // Invalid domain
+        for domain in [
+            "",
+            "exa mple.com",
+            "-example.com",
+            "example..com",
+            "a@b.com",
+        ] {
+            let result = Email::builder()
+                .sender(EmailAddress::new("sender@example.com"))
+                .recipients(Recipients::to(vec![EmailAddress::new("to@example.com")]))
+                .subject("Test")
+                .body_text("Test")
+                .auto_message_id(domain)
+                .build();
+            assert!(matches!(
+                result,
+                Err(crate::error::OciError::ConfigError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_split_by_recipient_chunk() {
+        let addresses = |prefix: &str, n: usize| -> Vec<EmailAddress> {
+            (0..n)
+                .map(|i| EmailAddress::new(format!("{}{}@example.com", prefix, i)))
+                .collect()
+        };
+        let email = Email::builder()
+            .sender(EmailAddress::new("sender@example.com"))
+            .recipients(
+                Recipients::builder()
+                    .to(addresses("to", 100))
+                    .cc(addresses("cc", 15))
+                    .bcc(addresses("bcc", 5))
+                    .build(),
+            )
+            .subject("Test")
+            .body_text("Test")
+            .build()
+            .unwrap();
+        assert_eq!(email.recipients.count(), 120);
+
+        let chunks = email.split_by_recipient_chunk(50);
+        let sizes: Vec<usize> = chunks.iter().map(|c| c.recipients.count()).collect();
+        assert_eq!(sizes, [50, 50, 20]);
+
+        // Recipients keep their field and order; other fields are copied
+        assert_eq!(chunks[0].recipients.to, Some(addresses("to", 50)));
+        assert_eq!(
+            chunks[1].recipients.to.as_ref().unwrap()[0].email,
+            "to50@example.com"
+        );
+        assert!(chunks[1].recipients.cc.is_none());
+        assert!(chunks[2].recipients.to.is_none());
+        assert_eq!(chunks[2].recipients.cc, Some(addresses("cc", 15)));
+        assert_eq!(chunks[2].recipients.bcc, Some(addresses("bcc", 5)));
+        for chunk in &chunks {
+            assert_eq!(chunk.subject, email.subject);
+            assert_eq!(chunk.sender, email.sender);
+            assert!(chunk.local_issues().is_empty());
+        }
+
+        // Uneven chunks; max is clamped to MAX_RECIPIENTS
+        let sizes: Vec<usize> = email
+            .split_by_recipient_chunk(7)
+            .iter()
+            .map(|c| c.recipients.count())
+            .collect();
+        assert_eq!(sizes.len(), 18);
+        assert!(sizes[..17].iter().all(|&n| n == 7));
+        assert_eq!(sizes[17], 1);
+        assert_eq!(email.split_by_recipient_chunk(1000).len(), 3);
+
+        // max = 0 is clamped to one recipient per chunk
+        let chunks_of_one = email.split_by_recipient_chunk(0);
+        assert_eq!(chunks_of_one.len(), 120);
+        assert!(chunks_of_one.iter().all(|c| c.recipients.count() == 1));
+
+        // Small enough: one copy
+        assert_eq!(
+            chunks[0].split_by_recipient_chunk(50),
+            vec![chunks[0].clone()]
+        );
+    }
+
+    #[test]
+    fn test_split_by_recipient_chunk_message_ids() {
+        let bcc: Vec<EmailAddress> = (0..120)
+            .map(|i| EmailAddress::new(format!("user{}@example.com", i)))
+            .collect();
+        let builder = || {
+            Email::builder()
+                .sender(EmailAddress::new("sender@example.com"))
+                .recipients(Recipients::bcc(bcc.clone()))
+                .subject("Test")
+                .body_text("Test")
+        };
+
+        // Each chunk gets its own ID in the original domain
+        let email = builder()
+            .auto_message_id("mail.example.com")
+            .build()
+            .unwrap();
+        let ids: Vec<String> = email
+            .split_by_recipient_chunk(MAX_RECIPIENTS)
+            .into_iter()
+            .map(|c| c.message_id.unwrap())
+            .collect();
+        assert_eq!(ids.len(), 3);
+        for (i, id) in ids.iter().enumerate() {
+            assert!(id.starts_with('<') && id.ends_with("@mail.example.com>"));
+            assert_ne!(Some(id), email.message_id.as_ref());
+            assert!(!ids[..i].contains(id));
+        }
+
+        // No domain to keep: OCI generates the IDs
+        let email = builder().message_id("no-domain").build().unwrap();
+        assert!(
+            email
+                .split_by_recipient_chunk(MAX_RECIPIENTS)
+                .iter()
+                .all(|c| c.message_id.is_none())
+        );
+
+        // A single unchanged copy keeps its ID
+        let email = builder()
+            .auto_message_id("mail.example.com")
+            .build()
+            .unwrap();
+        let small = &email.split_by_recipient_chunk(MAX_RECIPIENTS)[0];
+        assert_eq!(
+            small.split_by_recipient_chunk(MAX_RECIPIENTS),
+            vec![small.clone()]
+        );
+    }
+
+    #[test]
+    fn test_list_unsubscribe() {
+        let builder = || {
+            Email::builder()
+                .sender(EmailAddress::new("sender@example.com"))
+                .recipients(Recipients::to(vec![EmailAddress::new("to@example.com")]))
+                .subject("Newsletter")
+                .body_text("Hello")
+        };
+
+        // mailto only: no one-click POST header
+        let email = builder()
+            .list_unsubscribe("mailto:unsubscribe@example.com?subject=unsubscribe")
+            .build()
+            .unwrap();
+        let headers = email.headers.unwrap();
+        assert_eq!(
+            headers["List-Unsubscribe"],
+            "<mailto:unsubscribe@example.com?subject=unsubscribe>"
+        );
+        assert!(!headers.contains_key("List-Unsubscribe-Post"));
+
+        // https and mailto
+        let email = builder()
+            .list_unsubscribe("https://example.com/unsubscribe?id=123")
+            .list_unsubscribe("mailto:unsubscribe@example.com")
+            .build()
+            .unwrap();
+        let headers = email.headers.unwrap();
+        assert_eq!(
+            headers["List-Unsubscribe"],
+            "<https://example.com/unsubscribe?id=123>, <mailto:unsubscribe@example.com>"
+        );
+        assert_eq!(
+            headers["List-Unsubscribe-Post"],
+            "List-Unsubscribe=One-Click"
+        );
+
+        // Invalid targets
+        for target in [
+            "",
+            "example.com/unsubscribe",
+            "http://example.com/unsubscribe",
+            "ftp://example.com",
+            "mailto:",
+            "mailto:not-an-address",
+        ] {
+            let result = builder().list_unsubscribe(target).build();
+            assert!(
+                matches!(result, Err(crate::error::OciError::ConfigError(_))),
+                "{target}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_body_html_template() {
+        let template = Template::new("<p>Hi {{name}}</p>");
+        let mut context = HashMap::new();
+        context.insert("name".to_string(), "<Tom>".to_string());
+
+        let email = Email::builder()
+            .sender(EmailAddress::new("sender@example.com"))
+            .recipients(Recipients::to(vec![EmailAddress::new("to@example.com")]))
+            .subject("Test")
+            .body_html_template(&template, &context)
+            .build()
+            .unwrap();
+
+        assert_eq!(email.body_html.as_deref(), Some("<p>Hi &lt;Tom&gt;</p>"));
+    }
+
+    fn email_configuration(smtp_submit_endpoint: &str) -> EmailConfiguration {
+        EmailConfiguration {
+            compartment_id: "ocid1.compartment.oc1..test".to_string(),
+            http_submit_endpoint: "cell0.submit.email.ap-seoul-1.oci.oraclecloud.com".to_string(),
+            smtp_submit_endpoint: smtp_submit_endpoint.to_string(),
+            email_delivery_config_id: None,
+        }
+    }
+
+    #[test]
+    fn test_smtp_settings() {
+        let settings = email_configuration("smtp.email.ap-seoul-1.oci.oraclecloud.com")
+            .smtp_settings("ocid1.user.oc1..test@ocid1.tenancy.oc1..test", "secret")
+            .unwrap();
+        assert_eq!(settings.host, "smtp.email.ap-seoul-1.oci.oraclecloud.com");
+        assert_eq!(settings.port, 587);
+        assert!(settings.starttls);
+        assert_eq!(
+            settings.username,
+            "ocid1.user.oc1..test@ocid1.tenancy.oc1..test"
+        );
+        assert_eq!(settings.password, "secret");
+        assert!(!format!("{:?}", settings).contains("secret"));
+
+        let settings = email_configuration("smtp://smtp.email.ap-seoul-1.oci.oraclecloud.com:465")
+            .smtp_settings("user", "secret")
+            .unwrap();
+        assert_eq!(settings.host, "smtp.email.ap-seoul-1.oci.oraclecloud.com");
+        assert_eq!(settings.port, 465);
+        assert!(!settings.starttls);
+
+        for endpoint in ["", "smtp.example.com:abc"] {
+            assert!(matches!(
+                email_configuration(endpoint).smtp_settings("user", "secret"),
+                Err(crate::error::OciError::ConfigError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_html_to_text() {
+        let html = r#"<html><head><style>p { color: red; }</style></head>
+<body>
+  <h1>Welcome, Alice</h1>
+  <p>Your order <b>#1234</b> has shipped.<br>Items:</p>
+  <ul><li>Book &amp; pen</li><li>Lamp</li></ul>
+  <script>alert("x")</script>
+  <p>Questions? <a href="https://example.com/help">Contact&nbsp;us</a> &lt;today&gt;</p>
+</body></html>"#;
+
+        assert_eq!(
+            html_to_text(html),
+            "Welcome, Alice\nYour order #1234 has shipped.\nItems:\n- Book & pen\n- Lamp\nQuestions? Contact us <today>"
+        );
+    }
+
+    #[test]
+    fn test_auto_text_from_html() {
+        let builder = || {
+            Email::builder()
+                .sender(EmailAddress::new("sender@example.com"))
+                .recipients(Recipients::to(vec![EmailAddress::new("to@example.com")]))
+                .subject("Test")
+                .body_html("<p>Hello <b>World</b></p>")
+        };
+
+        // Disabled by default
+        assert_eq!(builder().build().unwrap().body_text, None);
+
+        let email = builder().auto_text_from_html(true).build().unwrap();
+        assert_eq!(email.body_text.as_deref(), Some("Hello World"));
+
+        // Explicit body_text is kept
+        let email = builder()
+            .body_text("Custom")
+            .auto_text_from_html(true)
+            .build()
+            .unwrap();
+        assert_eq!(email.body_text.as_deref(), Some("Custom"));
+    }
 }
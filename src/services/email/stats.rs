@@ -0,0 +1,463 @@
+//! Email delivery metrics
+//!
+//! Sending statistics of an approved sender, read from the Monitoring service
+//! (`oci_emaildelivery` namespace) with SummarizeMetricsData. Each metric is summed
+//! over the requested window:
+//!
+//! ```no_run
+//! # use oci_api::OciClient;
+//! # use oci_api::email::EmailStatsClient;
+//! # use std::time::Duration;
+//! # async fn example(oci_client: OciClient) -> oci_api::Result<()> {
+//! let stats = EmailStatsClient::new(oci_client);
+//! let counts = stats
+//!     .sent_count("ocid1.compartment.oc1..example", "noreply@example.com", Duration::from_secs(86400))
+//!     .await?;
+//! println!("{} relayed, {:.1}% bounced", counts.relayed, counts.bounce_rate() * 100.0);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::auth::Region;
+use crate::client::{OciClient, response_json};
+use crate::error::{OciError, Result};
+use reqwest::Method;
+use serde::Deserialize;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Monitoring namespace of Email Delivery metrics
+pub const NAMESPACE: &str = "oci_emaildelivery";
+
+/// Monitoring API version
+const MONITORING_API_VERSION: &str = "20180401";
+
+/// Metric dimension holding the sender address
+const SENDER_DIMENSION: &str = "sender";
+
+/// Longest window accepted by `sent_count` (Monitoring keeps 90 days of data)
+const MAX_WINDOW: Duration = Duration::from_secs(90 * 24 * 3600);
+
+/// Sending statistics of a sender over a time window (see `EmailStatsClient::sent_count`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SendingStats {
+    /// Emails accepted by Email Delivery (`EmailsAccepted`)
+    pub accepted: u64,
+
+    /// Emails relayed to the recipients' mail servers (`EmailsRelayed`)
+    pub relayed: u64,
+
+    /// Emails not sent to suppressed recipients (`EmailsSuppressed`)
+    pub suppressed: u64,
+
+    /// Permanent delivery failures (`HardBounces`)
+    pub hard_bounces: u64,
+
+    /// Temporary delivery failures (`SoftBounces`)
+    pub soft_bounces: u64,
+}
+
+impl SendingStats {
+    /// Hard and soft bounces per relayed email (0 if nothing was relayed)
+    pub fn bounce_rate(&self) -> f64 {
+        if self.relayed == 0 {
+            return 0.0;
+        }
+        (self.hard_bounces + self.soft_bounces) as f64 / self.relayed as f64
+    }
+}
+
+/// Email delivery metrics client
+///
+/// Requests go to the Monitoring endpoint (`telemetry.{region}.oraclecloud.com`, or the
+/// `monitoring` endpoint override) and need `read metrics` permission in the compartment.
+#[derive(Clone)]
+pub struct EmailStatsClient {
+    /// OCI HTTP client
+    oci_client: OciClient,
+}
+
+impl EmailStatsClient {
+    /// Create new Email stats client
+    ///
+    /// # Arguments
+    /// * `oci_client` - OCI HTTP client
+    pub fn new(oci_client: OciClient) -> Self {
+        Self { oci_client }
+    }
+
+    /// Sending statistics of `sender` over the last `window`
+    ///
+    /// Queries each metric (one request per metric, concurrently) and sums its datapoints.
+    ///
+    /// # Arguments
+    /// * `compartment_id` - Compartment OCID of the approved sender
+    /// * `sender` - Sender email address
+    /// * `window` - Time window ending now (at least 1 minute, at most 90 days)
+    pub async fn sent_count(
+        &self,
+        compartment_id: impl AsRef<str>,
+        sender: impl AsRef<str>,
+        window: Duration,
+    ) -> Result<SendingStats> {
+        if window < Duration::from_secs(60) || window > MAX_WINDOW {
+            return Err(OciError::ConfigError(format!(
+                "Stats window must be between 1 minute and 90 days, got {}s",
+                window.as_secs()
+            )));
+        }
+
+        let end = SystemTime::now();
+        let request = SummarizeRequest {
+            compartment_id: compartment_id.as_ref(),
+            sender: sender.as_ref(),
+            start_time: rfc3339(end - window),
+            end_time: rfc3339(end),
+            interval: interval(window),
+        };
+
+        let (accepted, relayed, suppressed, hard_bounces, soft_bounces) = tokio::try_join!(
+            self.metric_sum(&request, "EmailsAccepted"),
+            self.metric_sum(&request, "EmailsRelayed"),
+            self.metric_sum(&request, "EmailsSuppressed"),
+            self.metric_sum(&request, "HardBounces"),
+            self.metric_sum(&request, "SoftBounces"),
+        )?;
+
+        Ok(SendingStats {
+            accepted,
+            relayed,
+            suppressed,
+            hard_bounces,
+            soft_bounces,
+        })
+    }
+
+    /// Sum of all datapoints of `metric` (SummarizeMetricsData)
+    async fn metric_sum(&self, request: &SummarizeRequest<'_>, metric: &str) -> Result<u64> {
+        let path = format!(
+            "/{}/metrics/actions/summarizeMetricsData?compartmentId={}",
+            MONITORING_API_VERSION, request.compartment_id
+        );
+        let body = serde_json::json!({
+            "namespace": NAMESPACE,
+            "query": request.query(metric),
+            "startTime": request.start_time,
+            "endTime": request.end_time,
+        });
+
+        let response = self
+            .oci_client
+            .execute(
+                "monitoring",
+                Method::POST,
+                &self.endpoint(),
+                &path,
+                Some(serde_json::to_string(&body)?),
+            )
+            .await?;
+
+        let series: Vec<MetricData> = response_json(response).await?;
+        let sum: f64 = series
+            .iter()
+            .flat_map(|data| &data.aggregated_datapoints)
+            .map(|point| point.value)
+            .sum();
+        Ok(sum.max(0.0).round() as u64)
+    }
+
+    /// Monitoring endpoint (override `monitoring` or regional host)
+    fn endpoint(&self) -> String {
+        self.oci_client.endpoint(
+            "monitoring",
+            monitoring_host(&self.oci_client.region_typed()),
+        )
+    }
+}
+
+/// Parameters shared by the metric queries of one `sent_count` call
+struct SummarizeRequest<'a> {
+    compartment_id: &'a str,
+    sender: &'a str,
+    start_time: String,
+    end_time: String,
+    interval: &'static str,
+}
+
+impl SummarizeRequest<'_> {
+    /// MQL query summing `metric` of the sender per interval
+    fn query(&self, metric: &str) -> String {
+        format!(
+            "{}[{}]{{{} = \"{}\"}}.sum()",
+            metric,
+            self.interval,
+            SENDER_DIMENSION,
+            self.sender.replace('\\', "\\\\").replace('"', "\\\"")
+        )
+    }
+}
+
+/// Metric series returned by SummarizeMetricsData
+#[derive(Debug, Deserialize)]
+struct MetricData {
+    #[serde(rename = "aggregatedDatapoints", default)]
+    aggregated_datapoints: Vec<AggregatedDatapoint>,
+}
+
+/// Datapoint of a metric series
+#[derive(Debug, Deserialize)]
+struct AggregatedDatapoint {
+    value: f64,
+}
+
+/// Monitoring host for `region`
+fn monitoring_host(region: &Region) -> String {
+    format!("telemetry.{}.oraclecloud.com", region)
+}
+
+/// Query interval for `window`: one minute up to a day, then hourly
+///
+/// Keeps the number of datapoints per series within Monitoring limits.
+fn interval(window: Duration) -> &'static str {
+    if window <= Duration::from_secs(24 * 3600) {
+        "1m"
+    } else {
+        "1h"
+    }
+}
+
+/// RFC 3339 UTC timestamp of `time`, to the second (e.g. `2024-05-01T12:00:00Z`)
+fn rfc3339(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let (days, secs_of_day) = (secs / 86400, secs % 86400);
+
+    // Civil date from days since 1970-01-01 (proleptic Gregorian calendar)
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_utils::test_config;
+    use wiremock::matchers::{body_partial_json, method, path, query_param};
+    use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+    const COMPARTMENT_ID: &str = "ocid1.compartment.oc1..test";
+
+    fn stats_client(server: &MockServer) -> EmailStatsClient {
+        let oci_client = OciClient::builder(&test_config())
+            .endpoint_override("monitoring", server.uri())
+            .build()
+            .unwrap();
+        EmailStatsClient::new(oci_client)
+    }
+
+    /// Mount a canned SummarizeMetricsData response for `metric`
+    async fn mount_metric(server: &MockServer, metric: &str, series: serde_json::Value) {
+        Mock::given(method("POST"))
+            .and(path("/20180401/metrics/actions/summarizeMetricsData"))
+            .and(query_param("compartmentId", COMPARTMENT_ID))
+            .and(body_partial_json(serde_json::json!({
+                "namespace": "oci_emaildelivery",
+                "query": format!(r#"{}[1m]{{sender = "noreply@example.com"}}.sum()"#, metric),
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(series))
+            .expect(1)
+            .mount(server)
+            .await;
+    }
+
+    fn series(values: &[f64]) -> serde_json::Value {
+        let points: Vec<_> = values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| {
+                serde_json::json!({
+                    "timestamp": format!("2024-05-01T12:{:02}:00.000Z", i),
+                    "value": value,
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "namespace": "oci_emaildelivery",
+            "compartmentId": COMPARTMENT_ID,
+            "name": "metric",
+            "dimensions": {"sender": "noreply@example.com"},
+            "aggregatedDatapoints": points,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_sent_count() {
+        let server = MockServer::start().await;
+        mount_metric(
+            &server,
+            "EmailsAccepted",
+            serde_json::json!([series(&[10.0, 5.0]), series(&[5.0])]),
+        )
+        .await;
+        mount_metric(
+            &server,
+            "EmailsRelayed",
+            serde_json::json!([series(&[18.0])]),
+        )
+        .await;
+        mount_metric(
+            &server,
+            "EmailsSuppressed",
+            serde_json::json!([series(&[2.0])]),
+        )
+        .await;
+        mount_metric(
+            &server,
+            "HardBounces",
+            serde_json::json!([series(&[1.0, 2.0])]),
+        )
+        .await;
+        mount_metric(&server, "SoftBounces", serde_json::json!([])).await;
+
+        let stats = stats_client(&server)
+            .sent_count(
+                COMPARTMENT_ID,
+                "noreply@example.com",
+                Duration::from_secs(3600),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            stats,
+            SendingStats {
+                accepted: 20,
+                relayed: 18,
+                suppressed: 2,
+                hard_bounces: 3,
+                soft_bounces: 0,
+            }
+        );
+        assert!((stats.bounce_rate() - 3.0 / 18.0).abs() < 1e-9);
+
+        // The window ends now and spans one hour
+        let requests: Vec<Request> = server.received_requests().await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        let start = parse_rfc3339(body["startTime"].as_str().unwrap());
+        let end = parse_rfc3339(body["endTime"].as_str().unwrap());
+        assert_eq!(
+            end.duration_since(start).unwrap(),
+            Duration::from_secs(3600)
+        );
+    }
+
+    /// Parse the `YYYY-MM-DDTHH:MM:SSZ` timestamps written by `rfc3339`
+    fn parse_rfc3339(timestamp: &str) -> SystemTime {
+        let days_in = |y: u64, m: u64| match m {
+            2 if y.is_multiple_of(4) && (!y.is_multiple_of(100) || y.is_multiple_of(400)) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        };
+        let field = |range: std::ops::Range<usize>| timestamp[range].parse::<u64>().unwrap();
+        let (year, month, day) = (field(0..4), field(5..7), field(8..10));
+        let days = (1970..year)
+            .map(|y| if days_in(y, 2) == 29 { 366 } else { 365 })
+            .sum::<u64>()
+            + (1..month).map(|m| days_in(year, m)).sum::<u64>()
+            + day
+            - 1;
+        let secs = days * 86400 + field(11..13) * 3600 + field(14..16) * 60 + field(17..19);
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[tokio::test]
+    async fn test_sent_count_api_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "code": "NotAuthorizedOrNotFound",
+                "message": "Authorization failed or requested resource not found.",
+            })))
+            .mount(&server)
+            .await;
+
+        let error = stats_client(&server)
+            .sent_count(
+                COMPARTMENT_ID,
+                "noreply@example.com",
+                Duration::from_secs(600),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(error, OciError::ApiError { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_sent_count_window_bounds() {
+        let server = MockServer::start().await;
+        let client = stats_client(&server);
+
+        for window in [Duration::from_secs(59), MAX_WINDOW + Duration::from_secs(1)] {
+            let error = client
+                .sent_count(COMPARTMENT_ID, "noreply@example.com", window)
+                .await
+                .unwrap_err();
+            assert!(matches!(error, OciError::ConfigError(_)));
+        }
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_query() {
+        let request = SummarizeRequest {
+            compartment_id: COMPARTMENT_ID,
+            sender: r#"odd"name@example.com"#,
+            start_time: String::new(),
+            end_time: String::new(),
+            interval: "1h",
+        };
+        assert_eq!(
+            request.query("HardBounces"),
+            r#"HardBounces[1h]{sender = "odd\"name@example.com"}.sum()"#
+        );
+        assert_eq!(interval(Duration::from_secs(86400)), "1m");
+        assert_eq!(interval(Duration::from_secs(86401)), "1h");
+    }
+
+    #[test]
+    fn test_rfc3339() {
+        assert_eq!(rfc3339(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        let at = |secs: u64| rfc3339(UNIX_EPOCH + Duration::from_secs(secs));
+        assert_eq!(at(1_714_564_800), "2024-05-01T12:00:00Z");
+        assert_eq!(at(1_709_164_799), "2024-02-28T23:59:59Z");
+        assert_eq!(at(1_709_251_199), "2024-02-29T23:59:59Z");
+        assert_eq!(at(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(at(4_102_444_800), "2100-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn test_monitoring_host() {
+        assert_eq!(
+            monitoring_host(&"ap-seoul-1".parse().unwrap()),
+            "telemetry.ap-seoul-1.oraclecloud.com"
+        );
+    }
+}
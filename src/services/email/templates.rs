@@ -0,0 +1,143 @@
+//! Simple `{{name}}` templates for email bodies
+//!
+//! A dependency-free placeholder substitution, not a templating engine:
+//! - `{{name}}` is replaced with the HTML-escaped value of `name`
+//! - `{{{name}}}` is replaced with the raw (unescaped) value
+//! - Missing variables are replaced with an empty string
+//! - Whitespace inside the braces is ignored (`{{ name }}`)
+
+use std::collections::HashMap;
+
+/// Email body template with `{{name}}` placeholders
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    source: String,
+}
+
+impl Template {
+    /// Create a template from source text
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+        }
+    }
+
+    /// Template source text
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Render the template with `context` values
+    ///
+    /// Values are HTML-escaped for `{{name}}` and inserted as-is for `{{{name}}}`.
+    /// Unclosed placeholders are kept as literal text.
+    pub fn render(&self, context: &HashMap<String, String>) -> String {
+        let mut output = String::with_capacity(self.source.len());
+        let mut rest = self.source.as_str();
+
+        while let Some(start) = rest.find("{{") {
+            output.push_str(&rest[..start]);
+            let tail = &rest[start..];
+
+            let (raw, open, close) = if tail.starts_with("{{{") {
+                (true, "{{{", "}}}")
+            } else {
+                (false, "{{", "}}")
+            };
+
+            let Some(end) = tail[open.len()..].find(close) else {
+                // Unclosed placeholder
+                output.push_str(tail);
+                return output;
+            };
+
+            let name = tail[open.len()..open.len() + end].trim();
+            let value = context.get(name).map(String::as_str).unwrap_or_default();
+            if raw {
+                output.push_str(value);
+            } else {
+                output.push_str(&escape_html(value));
+            }
+
+            rest = &tail[open.len() + end + close.len()..];
+        }
+
+        output.push_str(rest);
+        output
+    }
+}
+
+impl From<&str> for Template {
+    fn from(source: &str) -> Self {
+        Self::new(source)
+    }
+}
+
+impl From<String> for Template {
+    fn from(source: String) -> Self {
+        Self::new(source)
+    }
+}
+
+/// Escape `&`, `<`, `>`, `"` and `'` for HTML text and attribute values
+pub fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_render() {
+        let template = Template::new("<p>Hello, {{name}}! Your code is {{ code }}.</p>");
+        let rendered = template.render(&context(&[("name", "Alice"), ("code", "1234")]));
+        assert_eq!(rendered, "<p>Hello, Alice! Your code is 1234.</p>");
+    }
+
+    #[test]
+    fn test_render_escapes_html() {
+        let template = Template::new("<p>{{name}}</p><div>{{{html}}}</div>");
+        let rendered = template.render(&context(&[
+            ("name", "<script>alert('x') & \"y\"</script>"),
+            ("html", "<b>bold</b>"),
+        ]));
+        assert_eq!(
+            rendered,
+            "<p>&lt;script&gt;alert(&#39;x&#39;) &amp; &quot;y&quot;&lt;/script&gt;</p><div><b>bold</b></div>"
+        );
+    }
+
+    #[test]
+    fn test_render_missing_variable() {
+        let template = Template::new("Hello, {{name}}{{{suffix}}}!");
+        assert_eq!(template.render(&HashMap::new()), "Hello, !");
+    }
+
+    #[test]
+    fn test_render_unclosed_placeholder() {
+        let template = Template::new("Hello, {{name}} {{oops");
+        assert_eq!(
+            template.render(&context(&[("name", "Bob")])),
+            "Hello, Bob {{oops"
+        );
+    }
+}
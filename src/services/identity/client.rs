@@ -0,0 +1,444 @@
+//! Identity client
+
+use crate::auth::Region;
+use crate::client::{OciClient, Page, Paginator, response_json};
+use crate::error::Result;
+use crate::services::identity::models::*;
+use crate::utils::encoding::path_with_query;
+use reqwest::Method;
+
+/// Identity client
+///
+/// Manages API signing keys of users and lists compartments.
+///
+/// # Bootstrapping
+/// Identity requests are signed with the client's current API key, so an existing
+/// valid key is required to upload a new one. The very first key of a user must be
+/// added in the OCI console (or by another user with permission to manage the user's keys).
+#[derive(Clone)]
+pub struct IdentityClient {
+    /// OCI HTTP client
+    oci_client: OciClient,
+}
+
+impl IdentityClient {
+    /// Create new Identity client
+    ///
+    /// # Arguments
+    /// * `oci_client` - OCI HTTP client
+    pub fn new(oci_client: OciClient) -> Self {
+        Self { oci_client }
+    }
+
+    /// Upload (register) a public API key for a user
+    ///
+    /// Pair with `utils::generate_api_key` to create and register a key.
+    ///
+    /// # Arguments
+    /// * `user_id` - User OCID
+    /// * `public_key_pem` - Public key (PEM format)
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use oci_api::{OciClient, OciConfig};
+    /// # use oci_api::identity::IdentityClient;
+    /// # async fn example(config: OciConfig) -> oci_api::Result<()> {
+    /// let (private_pem, public_pem, fingerprint) = oci_api::utils::generate_api_key(2048)?;
+    ///
+    /// let identity = IdentityClient::new(OciClient::new(&config)?);
+    /// let key = identity.upload_api_key(&config.user_id, &public_pem).await?;
+    /// assert_eq!(key.fingerprint, fingerprint);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn upload_api_key(
+        &self,
+        user_id: impl AsRef<str>,
+        public_key_pem: impl AsRef<str>,
+    ) -> Result<ApiKeySummary> {
+        let body_json = serde_json::to_string(&CreateApiKeyDetails {
+            key: public_key_pem.as_ref(),
+        })?;
+        let response = self
+            .oci_client
+            .execute(
+                "identity",
+                Method::POST,
+                &self.endpoint(),
+                &api_keys_path(user_id.as_ref()),
+                Some(body_json),
+            )
+            .await?;
+
+        let api_key: ApiKeySummary = response_json(response).await?;
+        Ok(api_key)
+    }
+
+    /// List API keys of a user
+    ///
+    /// # Arguments
+    /// * `user_id` - User OCID
+    pub async fn list_api_keys(&self, user_id: impl AsRef<str>) -> Result<Vec<ApiKeySummary>> {
+        let response = self
+            .oci_client
+            .execute(
+                "identity",
+                Method::GET,
+                &self.endpoint(),
+                &api_keys_path(user_id.as_ref()),
+                None,
+            )
+            .await?;
+
+        let api_keys: Vec<ApiKeySummary> = response_json(response).await?;
+        Ok(api_keys)
+    }
+
+    /// List the compartments in a compartment
+    ///
+    /// With `recursive`, lists all compartments in the subtree of `compartment_id`
+    /// (`compartmentIdInSubtree`); OCI only supports this when `compartment_id` is the
+    /// tenancy. All pages are fetched (see `list_compartments_paginated`).
+    ///
+    /// # Arguments
+    /// * `compartment_id` - Parent compartment OCID (the tenancy OCID for the root)
+    /// * `recursive` - Include nested compartments at any depth
+    pub async fn list_compartments(
+        &self,
+        compartment_id: impl AsRef<str>,
+        recursive: bool,
+    ) -> Result<Vec<CompartmentSummary>> {
+        self.list_compartments_paginated(compartment_id.as_ref(), recursive)
+            .try_collect()
+            .await
+    }
+
+    /// Stream the compartments in a compartment, fetching pages as they are consumed
+    ///
+    /// Same as `list_compartments`, without loading every page up front.
+    ///
+    /// # Arguments
+    /// * `compartment_id` - Parent compartment OCID (the tenancy OCID for the root)
+    /// * `recursive` - Include nested compartments at any depth
+    pub fn list_compartments_paginated<'a>(
+        &'a self,
+        compartment_id: &'a str,
+        recursive: bool,
+    ) -> Paginator<'a, CompartmentSummary> {
+        Paginator::new(move |page| async move {
+            let path = compartments_path(compartment_id, recursive, page.as_deref());
+            let response = self
+                .oci_client
+                .execute("identity", Method::GET, &self.endpoint(), &path, None)
+                .await?;
+            Page::from_response(response).await
+        })
+    }
+
+    /// Delete an API key of a user
+    ///
+    /// Deleting the key the client is signing with makes later requests fail
+    /// with 401; rotate first (`OciClient::rotate_key`) and delete the old key last.
+    ///
+    /// With `if_match`, the key is deleted only if its ETag still matches; otherwise
+    /// `OciError::PreconditionFailed` is returned and the key is left intact.
+    ///
+    /// # Arguments
+    /// * `user_id` - User OCID
+    /// * `fingerprint` - Fingerprint of the key to delete
+    /// * `if_match` - Optional ETag of the key
+    pub async fn delete_api_key(
+        &self,
+        user_id: impl AsRef<str>,
+        fingerprint: impl AsRef<str>,
+        if_match: Option<&str>,
+    ) -> Result<()> {
+        let path = format!(
+            "{}/{}",
+            api_keys_path(user_id.as_ref()),
+            fingerprint.as_ref().replace(':', "%3A")
+        );
+        let headers: Vec<(&str, &str)> = if_match
+            .map(|etag| ("if-match", etag))
+            .into_iter()
+            .collect();
+
+        self.oci_client
+            .execute_with_headers(
+                "identity",
+                Method::DELETE,
+                &self.endpoint(),
+                &path,
+                None,
+                &headers,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Identity endpoint (override `identity` or regional host)
+    fn endpoint(&self) -> String {
+        self.oci_client
+            .endpoint("identity", identity_host(&self.oci_client.region_typed()))
+    }
+}
+
+/// Identity host for `region`
+fn identity_host(region: &Region) -> String {
+    format!("identity.{}.oci.oraclecloud.com", region)
+}
+
+/// Path of the API keys collection of a user
+fn api_keys_path(user_id: &str) -> String {
+    format!("/20160918/users/{}/apiKeys", user_id)
+}
+
+/// Path (with query string) of the list compartments API
+fn compartments_path(compartment_id: &str, recursive: bool, page: Option<&str>) -> String {
+    let mut query = vec![("compartmentId", compartment_id)];
+
+    if recursive {
+        // Subtree listing requires accessLevel
+        query.push(("compartmentIdInSubtree", "true"));
+        query.push(("accessLevel", "ANY"));
+    }
+
+    if let Some(page) = page {
+        query.push(("page", page));
+    }
+
+    path_with_query("/20160918/compartments", &query)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::test_utils::test_config;
+    use crate::error::OciError;
+    use wiremock::matchers::{body_json, header, header_exists, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const USER_ID: &str = "ocid1.user.oc1..test";
+
+    fn identity_client(server: &MockServer) -> IdentityClient {
+        let oci_client = OciClient::builder(&test_config())
+            .endpoint_override("identity", server.uri())
+            .build()
+            .unwrap();
+        IdentityClient::new(oci_client)
+    }
+
+    fn api_key_json(fingerprint: &str, state: &str) -> serde_json::Value {
+        serde_json::json!({
+            "keyId": format!("ocid1.tenancy.oc1..test/{}/{}", USER_ID, fingerprint),
+            "fingerprint": fingerprint,
+            "userId": USER_ID,
+            "lifecycleState": state,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_upload_api_key() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/20160918/users/ocid1.user.oc1..test/apiKeys"))
+            .and(body_json(serde_json::json!({ "key": "PUBLIC KEY" })))
+            .and(header_exists("authorization"))
+            .and(header_exists("x-content-sha256"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(api_key_json("aa:bb", "CREATING")),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let key = identity_client(&server)
+            .upload_api_key(USER_ID, "PUBLIC KEY")
+            .await
+            .unwrap();
+        assert_eq!(key.fingerprint, "aa:bb");
+        assert_eq!(key.lifecycle_state, ApiKeyLifecycleState::Creating);
+    }
+
+    #[tokio::test]
+    async fn test_list_api_keys() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/20160918/users/ocid1.user.oc1..test/apiKeys"))
+            .and(header_exists("authorization"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                api_key_json("aa:bb", "ACTIVE"),
+                api_key_json("cc:dd", "INACTIVE"),
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let keys = identity_client(&server)
+            .list_api_keys(USER_ID)
+            .await
+            .unwrap();
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0].fingerprint, "aa:bb");
+        assert_eq!(keys[1].lifecycle_state, ApiKeyLifecycleState::Inactive);
+    }
+
+    #[tokio::test]
+    async fn test_delete_api_key() {
+        let server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .and(path("/20160918/users/ocid1.user.oc1..test/apiKeys/aa%3Abb"))
+            .and(header_exists("authorization"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        identity_client(&server)
+            .delete_api_key(USER_ID, "aa:bb", None)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_delete_api_key_not_found() {
+        let server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "code": "NotAuthorizedOrNotFound",
+                "message": "Key not found",
+            })))
+            .mount(&server)
+            .await;
+
+        let result = identity_client(&server)
+            .delete_api_key(USER_ID, "aa:bb", None)
+            .await;
+        match result.unwrap_err() {
+            OciError::ApiError { code, message, .. } => {
+                assert!(code.starts_with("404"));
+                assert_eq!(message, "Key not found");
+            }
+            e => panic!("Expected ApiError, got: {:?}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delete_api_key_precondition_failed() {
+        let server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .and(header("if-match", "etag-1"))
+            .respond_with(ResponseTemplate::new(412).set_body_json(serde_json::json!({
+                "code": "NoEtagMatch",
+                "message": "The resource has changed",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let result = identity_client(&server)
+            .delete_api_key(USER_ID, "aa:bb", Some("etag-1"))
+            .await;
+        assert!(matches!(result, Err(OciError::PreconditionFailed { .. })));
+    }
+
+    fn compartment_json(id: &str, parent: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "compartmentId": parent,
+            "name": id.rsplit('.').next().unwrap(),
+            "lifecycleState": "ACTIVE",
+        })
+    }
+
+    #[tokio::test]
+    async fn test_list_compartments() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/20160918/compartments"))
+            .and(query_param("compartmentId", "ocid1.tenancy.oc1..test"))
+            .and(header_exists("authorization"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                compartment_json("ocid1.compartment.oc1..a", "ocid1.tenancy.oc1..test"),
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let compartments = identity_client(&server)
+            .list_compartments("ocid1.tenancy.oc1..test", false)
+            .await
+            .unwrap();
+        assert_eq!(compartments.len(), 1);
+        assert_eq!(compartments[0].name, "a");
+
+        // No subtree flag unless recursive
+        let requests = server.received_requests().await.unwrap();
+        assert!(
+            !requests[0]
+                .url
+                .query()
+                .unwrap()
+                .contains("compartmentIdInSubtree")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_compartments_recursive_paginated() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/20160918/compartments"))
+            .and(query_param("compartmentIdInSubtree", "true"))
+            .and(query_param("accessLevel", "ANY"))
+            .and(query_param("page", "next+page/2="))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                compartment_json("ocid1.compartment.oc1..b1", "ocid1.compartment.oc1..b"),
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/20160918/compartments"))
+            .and(query_param("compartmentIdInSubtree", "true"))
+            .and(query_param("accessLevel", "ANY"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("opc-next-page", "next+page/2=")
+                    .set_body_json(serde_json::json!([
+                        compartment_json("ocid1.compartment.oc1..a", "ocid1.tenancy.oc1..test"),
+                        compartment_json("ocid1.compartment.oc1..b", "ocid1.tenancy.oc1..test"),
+                    ])),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let compartments = identity_client(&server)
+            .list_compartments("ocid1.tenancy.oc1..test", true)
+            .await
+            .unwrap();
+        let names: Vec<_> = compartments.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["a", "b", "b1"]);
+        assert_eq!(compartments[2].compartment_id, "ocid1.compartment.oc1..b");
+    }
+
+    #[test]
+    fn test_identity_paths() {
+        assert_eq!(
+            identity_host(&Region::ApSeoul1),
+            "identity.ap-seoul-1.oci.oraclecloud.com"
+        );
+        assert_eq!(
+            identity_host(&Region::Other("xx-newcity-1".to_string())),
+            "identity.xx-newcity-1.oci.oraclecloud.com"
+        );
+        assert_eq!(
+            api_keys_path("ocid1.user.oc1..test"),
+            "/20160918/users/ocid1.user.oc1..test/apiKeys"
+        );
+        assert_eq!(
+            compartments_path("ocid1.tenancy.oc1..test", true, Some("a+b/c=")),
+            "/20160918/compartments?accessLevel=ANY&compartmentId=ocid1.tenancy.oc1..test&compartmentIdInSubtree=true&page=a%2Bb%2Fc%3D"
+        );
+    }
+}
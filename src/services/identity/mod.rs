@@ -0,0 +1,6 @@
+// Identity 서비스 모듈
+pub mod client;
+pub mod models;
+
+pub use client::IdentityClient;
+pub use models::*;
@@ -0,0 +1,135 @@
+//! Identity API data models
+
+use serde::{Deserialize, Serialize};
+
+/// API signing key of a user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKeySummary {
+    /// Key ID (`{tenancy_id}/{user_id}/{fingerprint}`)
+    #[serde(rename = "keyId")]
+    pub key_id: String,
+
+    /// Key fingerprint (e.g., `aa:bb:cc:...:ff`)
+    pub fingerprint: String,
+
+    /// Lifecycle state
+    #[serde(rename = "lifecycleState")]
+    pub lifecycle_state: ApiKeyLifecycleState,
+}
+
+/// API key lifecycle state
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ApiKeyLifecycleState {
+    /// Creating
+    Creating,
+    /// Active
+    Active,
+    /// Inactive
+    Inactive,
+    /// Deleting
+    Deleting,
+    /// Deleted
+    Deleted,
+}
+
+/// Compartment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompartmentSummary {
+    /// Compartment OCID
+    pub id: String,
+
+    /// Compartment name
+    pub name: String,
+
+    /// Lifecycle state
+    #[serde(rename = "lifecycleState")]
+    pub lifecycle_state: CompartmentLifecycleState,
+
+    /// Parent compartment OCID
+    #[serde(rename = "compartmentId")]
+    pub compartment_id: String,
+}
+
+/// Compartment lifecycle state
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CompartmentLifecycleState {
+    /// Creating
+    Creating,
+    /// Active
+    Active,
+    /// Inactive
+    Inactive,
+    /// Deleting
+    Deleting,
+    /// Deleted
+    Deleted,
+}
+
+/// Upload API key request body
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct CreateApiKeyDetails<'a> {
+    /// Public key (PEM format)
+    pub key: &'a str,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_key_summary_deserialization() {
+        let json = r#"{
+            "keyId": "ocid1.tenancy.oc1..aaa/ocid1.user.oc1..bbb/aa:bb:cc",
+            "keyValue": "-----BEGIN PUBLIC KEY-----\n...\n-----END PUBLIC KEY-----",
+            "fingerprint": "aa:bb:cc",
+            "userId": "ocid1.user.oc1..bbb",
+            "timeCreated": "2024-01-01T00:00:00.000Z",
+            "lifecycleState": "ACTIVE",
+            "inactiveStatus": null
+        }"#;
+
+        let key: ApiKeySummary = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            key.key_id,
+            "ocid1.tenancy.oc1..aaa/ocid1.user.oc1..bbb/aa:bb:cc"
+        );
+        assert_eq!(key.fingerprint, "aa:bb:cc");
+        assert_eq!(key.lifecycle_state, ApiKeyLifecycleState::Active);
+    }
+
+    #[test]
+    fn test_compartment_summary_deserialization() {
+        let json = r#"{
+            "id": "ocid1.compartment.oc1..child",
+            "compartmentId": "ocid1.tenancy.oc1..root",
+            "name": "child",
+            "description": "Child compartment",
+            "timeCreated": "2024-01-01T00:00:00.000Z",
+            "lifecycleState": "ACTIVE",
+            "isAccessible": true
+        }"#;
+
+        let compartment: CompartmentSummary = serde_json::from_str(json).unwrap();
+        assert_eq!(compartment.id, "ocid1.compartment.oc1..child");
+        assert_eq!(compartment.name, "child");
+        assert_eq!(compartment.compartment_id, "ocid1.tenancy.oc1..root");
+        assert_eq!(
+            compartment.lifecycle_state,
+            CompartmentLifecycleState::Active
+        );
+    }
+
+    #[test]
+    fn test_create_api_key_details_serialization() {
+        let details = CreateApiKeyDetails {
+            key: "-----BEGIN PUBLIC KEY-----\nabc\n-----END PUBLIC KEY-----",
+        };
+        let json = serde_json::to_value(&details).unwrap();
+        assert_eq!(
+            json["key"],
+            "-----BEGIN PUBLIC KEY-----\nabc\n-----END PUBLIC KEY-----"
+        );
+    }
+}
@@ -1,2 +1,3 @@
 // OCI 서비스들
 pub mod email;
+pub mod identity;
@@ -0,0 +1,171 @@
+//! Encoding helpers
+//!
+//! Standard base64 and the base64 SHA-256 digest used for the `x-content-sha256`
+//! header of signed requests. Useful when signing requests for services this crate
+//! doesn't cover.
+
+use crate::error::Result;
+use base64::{Engine, engine::general_purpose};
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// Base64 (standard, padded) SHA-256 digest of a request body
+///
+/// This is the `x-content-sha256` header value of OCI signed requests.
+///
+/// # Example
+/// ```
+/// # use oci_api::utils::body_sha256_base64;
+/// assert_eq!(
+///     body_sha256_base64(b""),
+///     "47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU="
+/// );
+/// ```
+pub fn body_sha256_base64(body: &[u8]) -> String {
+    base64_encode(Sha256::digest(body))
+}
+
+/// Length and base64 SHA-256 digest of everything `reader` yields
+///
+/// The first pass of a streamed upload (see `client::StreamBody::from_reader`):
+/// the body must then be read again from the start to send it.
+pub async fn body_sha256_base64_reader<R: AsyncRead + Unpin>(
+    mut reader: R,
+) -> Result<(u64, String)> {
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    let mut length = 0u64;
+
+    loop {
+        let n = reader.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+        length += n as u64;
+    }
+
+    Ok((length, base64_encode(hasher.finalize())))
+}
+
+/// Encode bytes as standard, padded base64
+pub fn base64_encode(data: impl AsRef<[u8]>) -> String {
+    general_purpose::STANDARD.encode(data)
+}
+
+/// Percent-encode a query parameter value (opaque tokens such as `opc-next-page`)
+pub(crate) fn encode_query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Path with a canonical query string: keys and values percent-encoded, sorted by key
+///
+/// The result is both signed as `(request-target)` and sent, so the two can't differ.
+/// Pairs with the same key keep their relative order.
+pub(crate) fn path_with_query(path: &str, query: &[(&str, &str)]) -> String {
+    if query.is_empty() {
+        return path.to_string();
+    }
+
+    let mut pairs = query.to_vec();
+    pairs.sort_by_key(|(key, _)| *key);
+    let query = pairs
+        .iter()
+        .map(|(key, value)| format!("{}={}", encode_query_value(key), encode_query_value(value)))
+        .collect::<Vec<_>>()
+        .join("&");
+    format!("{}?{}", path, query)
+}
+
+/// Decode standard, padded base64
+///
+/// Malformed input is returned as `OciError::Base64Error`.
+pub fn base64_decode(encoded: &str) -> Result<Vec<u8>> {
+    Ok(general_purpose::STANDARD.decode(encoded)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_body_sha256_base64() {
+        // SHA-256 test vectors (FIPS 180-2)
+        assert_eq!(
+            body_sha256_base64(b""),
+            "47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU="
+        );
+        assert_eq!(
+            body_sha256_base64(b"abc"),
+            "ungWv48Bz+pBQUDeXa4iI7ADYaOWF3qctBD/YfIAFa0="
+        );
+    }
+
+    #[tokio::test]
+    async fn test_body_sha256_base64_reader() {
+        let (length, digest) = body_sha256_base64_reader(&b"abc"[..]).await.unwrap();
+        assert_eq!(length, 3);
+        assert_eq!(digest, body_sha256_base64(b"abc"));
+    }
+
+    #[test]
+    fn test_path_with_query() {
+        assert_eq!(path_with_query("/items", &[]), "/items");
+        assert_eq!(
+            path_with_query(
+                "/items",
+                &[
+                    ("page", "a+b/c=="),
+                    ("emailAddress", "user@example.com"),
+                    ("name", "a b&c=d?é"),
+                    ("compartmentId", "ocid1.compartment.oc1..a-b_c~d"),
+                ]
+            ),
+            "/items?compartmentId=ocid1.compartment.oc1..a-b_c~d&emailAddress=user%40example.com&name=a%20b%26c%3Dd%3F%C3%A9&page=a%2Bb%2Fc%3D%3D"
+        );
+
+        // Repeated keys keep their order
+        assert_eq!(
+            path_with_query("/items", &[("b", "1"), ("a", "2"), ("b", "0")]),
+            "/items?a=2&b=1&b=0"
+        );
+    }
+
+    #[test]
+    fn test_base64_round_trip() {
+        // RFC 4648 test vectors
+        for (decoded, encoded) in [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(base64_encode(decoded), encoded);
+            assert_eq!(base64_decode(encoded).unwrap(), decoded.as_bytes());
+        }
+
+        assert!(base64_decode("not base64!").is_err());
+    }
+
+    #[test]
+    fn test_base64_decode_malformed() {
+        for malformed in ["not base64!", "Zm9", "Zg=", "Zm9v===="] {
+            assert!(
+                matches!(
+                    base64_decode(malformed),
+                    Err(crate::error::OciError::Base64Error(_))
+                ),
+                "{malformed}"
+            );
+        }
+    }
+}
@@ -0,0 +1,94 @@
+//! Masking of identifiers
+//!
+//! Email addresses and OCIDs are PII or account identifiers. Error messages and logs
+//! show them masked, enough to tell values apart without exposing them; structured
+//! error fields (e.g. `OciError::Forbidden::compartment`) keep the full values.
+
+/// Number of trailing OCID characters kept by `mask_ocid`
+const OCID_VISIBLE_SUFFIX: usize = 4;
+
+/// Mask an email address, keeping the first character and the domain
+///
+/// # Example
+/// ```
+/// # use oci_api::utils::mask_email;
+/// assert_eq!(mask_email("john.doe@example.com"), "j***@example.com");
+/// assert_eq!(mask_email("not an address"), "***");
+/// ```
+pub fn mask_email(email: &str) -> String {
+    match email.rsplit_once('@') {
+        Some((local, domain)) => match local.chars().next() {
+            Some(first) => format!("{}***@{}", first, domain),
+            None => format!("***@{}", domain),
+        },
+        None => "***".to_string(),
+    }
+}
+
+/// Mask an OCID, keeping its prefix (type, realm and region) and the last 4 characters
+///
+/// Values that aren't OCIDs (e.g. `unknown`) are returned unchanged. Unique ids of
+/// 4 characters or less are masked entirely.
+///
+/// # Example
+/// ```
+/// # use oci_api::utils::mask_ocid;
+/// assert_eq!(
+///     mask_ocid("ocid1.compartment.oc1..aaaaaaaa5bqkqd7v"),
+///     "ocid1.compartment.oc1..***qd7v"
+/// );
+/// ```
+pub fn mask_ocid(ocid: &str) -> String {
+    let Some((prefix, unique_id)) = ocid
+        .strip_prefix("ocid1.")
+        .and_then(|_| ocid.rsplit_once('.'))
+    else {
+        return ocid.to_string();
+    };
+
+    let chars = unique_id.chars().count();
+    let suffix: String = if chars > OCID_VISIBLE_SUFFIX {
+        unique_id
+            .chars()
+            .skip(chars - OCID_VISIBLE_SUFFIX)
+            .collect()
+    } else {
+        String::new()
+    };
+    format!("{}.***{}", prefix, suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_email() {
+        assert_eq!(mask_email("john.doe@example.com"), "j***@example.com");
+        assert_eq!(mask_email("a@b.io"), "a***@b.io");
+        assert_eq!(mask_email("홍길동@example.kr"), "홍***@example.kr");
+        assert_eq!(mask_email("@example.com"), "***@example.com");
+        assert_eq!(mask_email("no-at-sign"), "***");
+        assert_eq!(mask_email(""), "***");
+    }
+
+    #[test]
+    fn test_mask_ocid() {
+        assert_eq!(
+            mask_ocid(
+                "ocid1.tenancy.oc1..aaaaaaaaba3pv6wkcr4jqae5f44n2b2m2yt2j6rx32uzr4h25vqstifsfdsq"
+            ),
+            "ocid1.tenancy.oc1..***fdsq"
+        );
+        assert_eq!(
+            mask_ocid("ocid1.instance.oc1.ap-seoul-1.anuwgljrabcdefgh"),
+            "ocid1.instance.oc1.ap-seoul-1.***efgh"
+        );
+        assert_eq!(mask_ocid("ocid1.user.oc1..abcd"), "ocid1.user.oc1..***");
+
+        // Not OCIDs
+        assert_eq!(mask_ocid("unknown"), "unknown");
+        assert_eq!(mask_ocid("ocid1"), "ocid1");
+        assert_eq!(mask_ocid(""), "");
+    }
+}
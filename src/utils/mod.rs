@@ -1 +1,14 @@
 // 유틸리티 함수들
+pub mod encoding;
+pub mod fingerprint;
+pub mod keygen;
+pub mod mask;
+pub mod ocid;
+pub mod retry;
+
+pub use encoding::{base64_decode, base64_encode, body_sha256_base64, body_sha256_base64_reader};
+pub use fingerprint::compute_fingerprint;
+pub use keygen::generate_api_key;
+pub use mask::{mask_email, mask_ocid};
+pub use ocid::{Ocid, parse_ocid};
+pub use retry::{RetryPolicy, retry_with_backoff};
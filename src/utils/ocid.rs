@@ -0,0 +1,181 @@
+//! OCID parsing
+//!
+//! Oracle Cloud IDs have the form `ocid1.<type>.<realm>.[region][.future use].<unique id>`.
+//! The region is empty for global resources such as tenancies, users and compartments
+//! (`ocid1.tenancy.oc1..aaaa...`).
+
+use crate::error::{OciError, Result};
+use std::fmt;
+use std::str::FromStr;
+
+/// OCID version prefix
+const PREFIX: &str = "ocid1";
+
+/// Parsed OCID
+///
+/// # Example
+/// ```
+/// # use oci_api::utils::Ocid;
+/// let ocid = Ocid::parse("ocid1.instance.oc1.ap-seoul-1.anuwgljrabcdefgh")?;
+/// assert_eq!(ocid.resource_type(), "instance");
+/// assert_eq!(ocid.realm(), "oc1");
+/// assert_eq!(ocid.region(), Some("ap-seoul-1"));
+/// assert_eq!(ocid.unique_id(), "anuwgljrabcdefgh");
+/// # Ok::<(), oci_api::OciError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Ocid {
+    /// Full OCID
+    value: String,
+    resource_type: String,
+    realm: String,
+    region: Option<String>,
+    unique_id: String,
+}
+
+impl Ocid {
+    /// Parse an OCID
+    ///
+    /// Returns `OciError::ConfigError` if `s` doesn't start with `ocid1.`, doesn't have
+    /// 5 or 6 dot-separated segments, or has an empty type, realm or unique id.
+    pub fn parse(s: &str) -> Result<Self> {
+        let invalid = |reason: &str| {
+            OciError::ConfigError(format!(
+                "Invalid OCID: '{}' ({})",
+                crate::utils::mask_ocid(s),
+                reason
+            ))
+        };
+
+        let segments: Vec<&str> = s.split('.').collect();
+        if segments[0] != PREFIX {
+            return Err(invalid("must start with 'ocid1.'"));
+        }
+        if !(5..=6).contains(&segments.len()) {
+            return Err(invalid(
+                "expected ocid1.<type>.<realm>.[region][.future use].<unique id>",
+            ));
+        }
+
+        let (resource_type, realm, region) = (segments[1], segments[2], segments[3]);
+        let unique_id = segments[segments.len() - 1];
+        if resource_type.is_empty() {
+            return Err(invalid("empty resource type"));
+        }
+        if realm.is_empty() {
+            return Err(invalid("empty realm"));
+        }
+        if unique_id.is_empty() {
+            return Err(invalid("empty unique id"));
+        }
+
+        Ok(Self {
+            value: s.to_string(),
+            resource_type: resource_type.to_string(),
+            realm: realm.to_string(),
+            region: (!region.is_empty()).then(|| region.to_string()),
+            unique_id: unique_id.to_string(),
+        })
+    }
+
+    /// Resource type (e.g., `tenancy`, `user`, `compartment`)
+    pub fn resource_type(&self) -> &str {
+        &self.resource_type
+    }
+
+    /// Realm (e.g., `oc1`)
+    pub fn realm(&self) -> &str {
+        &self.realm
+    }
+
+    /// Region (e.g., `ap-seoul-1`), `None` for global resources
+    pub fn region(&self) -> Option<&str> {
+        self.region.as_deref()
+    }
+
+    /// Unique id (last segment)
+    pub fn unique_id(&self) -> &str {
+        &self.unique_id
+    }
+
+    /// Full OCID
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+/// Parse an OCID (see `Ocid::parse`)
+pub fn parse_ocid(s: &str) -> Result<Ocid> {
+    Ocid::parse(s)
+}
+
+impl fmt::Display for Ocid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+impl FromStr for Ocid {
+    type Err = OciError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ocids() {
+        let user = Ocid::parse("ocid1.user.oc1..aaaaaaaaxyz").unwrap();
+        assert_eq!(user.resource_type(), "user");
+        assert_eq!(user.realm(), "oc1");
+        assert_eq!(user.region(), None);
+        assert_eq!(user.unique_id(), "aaaaaaaaxyz");
+
+        let tenancy: Ocid =
+            "ocid1.tenancy.oc1..aaaaaaaaba3pv6wkcr4jqae5f44n2b2m2yt2j6rx32uzr4h25vqstifsfdsq"
+                .parse()
+                .unwrap();
+        assert_eq!(tenancy.resource_type(), "tenancy");
+        assert_eq!(
+            tenancy.to_string(),
+            "ocid1.tenancy.oc1..aaaaaaaaba3pv6wkcr4jqae5f44n2b2m2yt2j6rx32uzr4h25vqstifsfdsq"
+        );
+
+        let compartment = Ocid::parse("ocid1.compartment.oc1..aaaaaaaa5bqkqd7v").unwrap();
+        assert_eq!(compartment.resource_type(), "compartment");
+        assert_eq!(compartment.unique_id(), "aaaaaaaa5bqkqd7v");
+
+        let instance = Ocid::parse("ocid1.instance.oc2.us-langley-1.anuwgljr").unwrap();
+        assert_eq!(instance.realm(), "oc2");
+        assert_eq!(instance.region(), Some("us-langley-1"));
+
+        // Future-use segment
+        let future = Ocid::parse("ocid1.volume.oc1.iad.future.abcd").unwrap();
+        assert_eq!(future.region(), Some("iad"));
+        assert_eq!(future.unique_id(), "abcd");
+    }
+
+    #[test]
+    fn test_parse_invalid_ocids() {
+        for s in [
+            "",
+            "unknown",
+            "ocid2.user.oc1..abcd",
+            "ocid1.user.oc1",
+            "ocid1.user.oc1.abcd",
+            "ocid1.a.b.c.d.e.f",
+            "ocid1..oc1..abcd",
+            "ocid1.user...abcd",
+            "ocid1.user.oc1..",
+        ] {
+            assert!(
+                matches!(Ocid::parse(s), Err(OciError::ConfigError(_))),
+                "{s}"
+            );
+        }
+    }
+}
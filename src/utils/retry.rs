@@ -0,0 +1,318 @@
+//! Retry with exponential backoff
+//!
+//! The retry loop behind `OciClientBuilder::retry_policy`, usable to wrap any OCI call:
+//!
+//! ```no_run
+//! # use oci_api::OciClient;
+//! # use oci_api::utils::retry::{RetryPolicy, retry_with_backoff};
+//! # async fn example(client: OciClient, endpoint: &str) -> oci_api::Result<()> {
+//! let policy = RetryPolicy::default();
+//! let response = retry_with_backoff(&policy, || {
+//!     client.execute_with_timeout("health", reqwest::Method::GET, endpoint, "/", None, None)
+//! })
+//! .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::Result;
+use std::future::Future;
+use std::time::{Duration, SystemTime};
+
+/// When and how long to wait between attempts (see `retry_with_backoff`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one (1 = no retry)
+    pub max_attempts: u32,
+
+    /// Delay before the first retry, doubling per retry
+    pub base_delay: Duration,
+
+    /// Upper bound of the delay
+    pub max_delay: Duration,
+
+    /// Randomize each delay between half and all of its value, so that concurrent
+    /// callers don't retry in lockstep
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    /// 3 attempts, 100 ms doubling up to 5 s, with jitter
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(5),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy that never retries
+    pub fn no_retry() -> Self {
+        Self {
+            max_attempts: 1,
+            jitter: false,
+            ..Self::default()
+        }
+    }
+
+    /// Delay before retry number `retry` (1-based): `base_delay * 2^(retry - 1)`, up to `max_delay`
+    ///
+    /// With `jitter`, a random delay between half and all of that value.
+    pub fn delay(&self, retry: u32) -> Duration {
+        let factor = 1u32 << retry.saturating_sub(1).min(16);
+        let delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+
+        if self.jitter {
+            delay.mul_f64(0.5 + rand::random::<f64>() * 0.5)
+        } else {
+            delay
+        }
+    }
+}
+
+/// Parse a `Retry-After` header value into the delay to wait
+///
+/// Accepts both forms of the header: a number of seconds (`120`) or an HTTP-date
+/// (`Wed, 21 Oct 2015 07:28:00 GMT`), taken relative to now. Negative values and dates
+/// in the past give a zero delay. Returns `None` for other values.
+///
+/// # Example
+/// ```
+/// # use oci_api::utils::retry::parse_retry_after;
+/// # use std::time::Duration;
+/// assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+/// assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), Some(Duration::ZERO));
+/// assert_eq!(parse_retry_after("soon"), None);
+/// ```
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    parse_retry_after_at(value, SystemTime::now())
+}
+
+/// `parse_retry_after` with HTTP-dates taken relative to `now`
+fn parse_retry_after_at(value: &str, now: SystemTime) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<i64>() {
+        return Some(Duration::from_secs(secs.max(0).unsigned_abs()));
+    }
+
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(date.duration_since(now).unwrap_or(Duration::ZERO))
+}
+
+/// Run `op` until it succeeds, fails with a non-retryable error or `policy.max_attempts` is reached
+///
+/// An error is retried if `OciError::is_retryable` is true (throttling and transient
+/// server errors). The last error is returned when all attempts fail.
+/// When the error carries a `Retry-After` delay (`OciError::retry_after`), the wait is
+/// the longer of that delay and the backoff delay. If `Retry-After` is longer than
+/// `policy.max_delay`, the error is returned without retrying, so that the caller can
+/// decide whether to wait that long instead of retrying while still throttled.
+///
+/// # Arguments
+/// * `policy` - Retry policy
+/// * `op` - Operation, called once per attempt
+pub async fn retry_with_backoff<F, Fut, T>(policy: &RetryPolicy, mut op: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Err(e)
+                if attempt < policy.max_attempts
+                    && e.is_retryable()
+                    && e.retry_after()
+                        .is_none_or(|delay| delay <= policy.max_delay) =>
+            {
+                let delay = match e.retry_after() {
+                    Some(retry_after) => policy.delay(attempt).max(retry_after),
+                    None => policy.delay(attempt),
+                };
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::OciError;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn api_error(status: &str) -> OciError {
+        OciError::ApiError {
+            code: status.to_string(),
+            message: "error".to_string(),
+            details: None,
+            trace: None,
+            retry_after: None,
+        }
+    }
+
+    fn with_retry_after(mut error: OciError, millis: u64) -> OciError {
+        if let OciError::ApiError { retry_after, .. } = &mut error {
+            *retry_after = Some(Duration::from_millis(millis));
+        }
+        error
+    }
+
+    fn fast_policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+            jitter: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_flaky_operation() {
+        let calls = AtomicU32::new(0);
+        let result = retry_with_backoff(&fast_policy(5), || async {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => Err(api_error("503 Service Unavailable")),
+                1 => Err(api_error("429 Too Many Requests")),
+                n => Ok(n),
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_exhausted_returns_last_error() {
+        let calls = AtomicU32::new(0);
+        let result: Result<()> = retry_with_backoff(&fast_policy(3), || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(api_error("500 Internal Server Error"))
+        })
+        .await;
+
+        assert!(matches!(result, Err(OciError::ApiError { .. })));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_no_retry_on_non_retryable_error() {
+        let calls = AtomicU32::new(0);
+        let result: Result<()> = retry_with_backoff(&fast_policy(3), || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(api_error("404 Not Found"))
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let result: Result<()> = retry_with_backoff(&RetryPolicy::no_retry(), || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(api_error("503 Service Unavailable"))
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_waits_for_retry_after() {
+        let calls = AtomicU32::new(0);
+        let start = std::time::Instant::now();
+        let result = retry_with_backoff(&fast_policy(2), || async {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => Err(with_retry_after(api_error("429 Too Many Requests"), 50)),
+                n => Ok(n),
+            }
+        })
+        .await;
+        // Longer than max_delay (5 ms): returned without retrying
+        match result.unwrap_err() {
+            e @ OciError::ApiError { .. } => {
+                assert_eq!(e.retry_after(), Some(Duration::from_millis(50)))
+            }
+            e => panic!("Expected ApiError, got: {:?}", e),
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(start.elapsed() < Duration::from_millis(50));
+
+        let policy = RetryPolicy {
+            max_delay: Duration::from_secs(1),
+            ..fast_policy(2)
+        };
+        let calls = AtomicU32::new(0);
+        let start = std::time::Instant::now();
+        retry_with_backoff(&policy, || async {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => Err(with_retry_after(api_error("503 Service Unavailable"), 50)),
+                n => Ok(n),
+            }
+        })
+        .await
+        .unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_parse_retry_after_seconds() {
+        assert_eq!(parse_retry_after("0"), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after(" 5 "), Some(Duration::from_secs(5)));
+        assert_eq!(parse_retry_after("-5"), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("1.5"), None);
+        assert_eq!(parse_retry_after(""), None);
+    }
+
+    #[test]
+    fn test_parse_retry_after_http_date() {
+        let now = httpdate::parse_http_date("Wed, 21 Oct 2015 07:28:00 GMT").unwrap();
+        assert_eq!(
+            parse_retry_after_at("Wed, 21 Oct 2015 07:30:00 GMT", now),
+            Some(Duration::from_secs(120))
+        );
+
+        // In the past
+        assert_eq!(
+            parse_retry_after_at("Wed, 21 Oct 2015 07:27:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(
+            parse_retry_after("Thu, 01 Jan 1970 00:00:00 GMT"),
+            Some(Duration::ZERO)
+        );
+
+        let in_a_minute = httpdate::fmt_http_date(SystemTime::now() + Duration::from_secs(60));
+        let delay = parse_retry_after(&in_a_minute).unwrap();
+        assert!(delay > Duration::from_secs(55) && delay <= Duration::from_secs(60));
+
+        assert_eq!(parse_retry_after_at("21 Oct 2015", now), None);
+    }
+
+    #[test]
+    fn test_delay() {
+        let policy = RetryPolicy {
+            jitter: false,
+            ..RetryPolicy::default()
+        };
+        assert_eq!(policy.delay(1), Duration::from_millis(100));
+        assert_eq!(policy.delay(2), Duration::from_millis(200));
+        assert_eq!(policy.delay(3), Duration::from_millis(400));
+        assert_eq!(policy.delay(100), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_delay_with_jitter() {
+        let policy = RetryPolicy::default();
+        for _ in 0..100 {
+            let delay = policy.delay(3);
+            assert!(delay >= Duration::from_millis(200) && delay <= Duration::from_millis(400));
+        }
+    }
+}
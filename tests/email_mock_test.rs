@@ -0,0 +1,1326 @@
+//! Email client tests against a local mock server
+//!
+//! Points `EmailClient` at a wiremock server via endpoint overrides and checks
+//! method, path, body and the request signature.
+
+use base64::{Engine, engine::general_purpose};
+use oci_api::email::{
+    Email, EmailAddress, EmailClient, Recipients, Sender, SenderFilter, SenderLifecycleState,
+    ValidationIssue,
+};
+use oci_api::{OciClient, OciConfig, OciError};
+use rsa::pkcs1v15::{Signature, VerifyingKey};
+use rsa::pkcs8::DecodePrivateKey;
+use rsa::signature::Verifier;
+use sha2::{Digest, Sha256};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+const TEST_KEY: &str = include_str!("fixtures/test_key.pem");
+const TENANCY_ID: &str = "ocid1.tenancy.oc1..test";
+const COMPARTMENT_ID: &str = "ocid1.compartment.oc1..test";
+
+fn test_config() -> OciConfig {
+    OciConfig {
+        user_id: "ocid1.user.oc1..test".to_string(),
+        tenancy_id: TENANCY_ID.to_string(),
+        region: "ap-seoul-1".to_string(),
+        fingerprint: "aa:bb:cc:dd:ee:ff".to_string(),
+        private_key: TEST_KEY.to_string(),
+        compartment_id: Some(COMPARTMENT_ID.to_string()),
+    }
+}
+
+/// Start a mock server that serves the email configuration
+async fn start_server() -> MockServer {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/20170907/configuration"))
+        .and(query_param("compartmentId", COMPARTMENT_ID))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "compartmentId": COMPARTMENT_ID,
+            "httpSubmitEndpoint": "cell0.submit.email.ap-seoul-1.oci.oraclecloud.com",
+            "smtpSubmitEndpoint": "smtp.email.ap-seoul-1.oci.oraclecloud.com",
+            "emailDeliveryConfigId": null,
+        })))
+        .mount(&server)
+        .await;
+
+    server
+}
+
+async fn email_client(server: &MockServer) -> EmailClient {
+    let oci_client = OciClient::builder(&test_config())
+        .endpoint_override("email.control", server.uri())
+        .endpoint_override("email.submit", server.uri())
+        .build()
+        .unwrap();
+
+    EmailClient::new(oci_client).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_resolved_endpoints() {
+    let server = start_server().await;
+    let oci_client = OciClient::builder(&test_config())
+        .endpoint_override("email.control", server.uri())
+        .build()
+        .unwrap();
+
+    let client = EmailClient::new(oci_client).await.unwrap();
+    assert_eq!(client.control_endpoint(), server.uri());
+    assert_eq!(
+        client.submit_endpoint(),
+        Some("cell0.submit.email.ap-seoul-1.oci.oraclecloud.com")
+    );
+}
+
+#[tokio::test]
+async fn test_compartment_discovered_from_email_configuration() {
+    const DISCOVERED: &str = "ocid1.compartment.oc1..discovered";
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/20170907/configuration"))
+        .and(query_param("compartmentId", TENANCY_ID))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "compartmentId": DISCOVERED,
+            "httpSubmitEndpoint": "cell0.submit.email.ap-seoul-1.oci.oraclecloud.com",
+            "smtpSubmitEndpoint": "smtp.email.ap-seoul-1.oci.oraclecloud.com",
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/20170907/senders"))
+        .and(query_param("compartmentId", DISCOVERED))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/20220926/actions/submitEmail"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "messageId": "msg-1",
+            "envelopeId": "env-1",
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // No compartment configured: the email configuration's compartment is used
+    let config = OciConfig {
+        compartment_id: None,
+        ..test_config()
+    };
+    let oci_client = OciClient::builder(&config)
+        .endpoint_override("email.control", server.uri())
+        .endpoint_override("email.submit", server.uri())
+        .build()
+        .unwrap();
+    let client = EmailClient::new(oci_client).await.unwrap();
+    assert_eq!(client.compartment_id(), DISCOVERED);
+
+    client.list_senders_default(None, None).await.unwrap();
+    client.send(email_from("sender@example.com")).await.unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    let submit = requests
+        .iter()
+        .find(|r| r.url.path() == "/20220926/actions/submitEmail")
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&submit.body).unwrap();
+    assert_eq!(body["sender"]["compartmentId"], DISCOVERED);
+}
+
+#[tokio::test]
+async fn test_new_lazy_loads_configuration_on_first_send() {
+    let server = start_server().await;
+    Mock::given(method("POST"))
+        .and(path("/20220926/actions/submitEmail"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "messageId": "msg-1",
+            "envelopeId": "env-1",
+        })))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let oci_client = OciClient::builder(&test_config())
+        .endpoint_override("email.control", server.uri())
+        .endpoint_override("email.submit", server.uri())
+        .build()
+        .unwrap();
+    let client = EmailClient::new_lazy(oci_client);
+    assert!(server.received_requests().await.unwrap().is_empty());
+    assert_eq!(client.submit_endpoint(), None);
+
+    let email = Email::builder()
+        .sender(EmailAddress::new("sender@example.com"))
+        .recipients(Recipients::to(vec![EmailAddress::new("to@example.com")]))
+        .subject("Hello")
+        .body_text("Hello")
+        .build()
+        .unwrap();
+
+    // Concurrent first sends (including a clone's) share a single configuration load
+    let clone = client.clone();
+    let (first, second) = tokio::join!(client.send(email.clone()), clone.send(email.clone()));
+    first.unwrap();
+    second.unwrap();
+    client.send(email).await.unwrap();
+
+    let paths: Vec<String> = server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|r| r.url.path().to_string())
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            "/20170907/configuration",
+            "/20220926/actions/submitEmail",
+            "/20220926/actions/submitEmail",
+            "/20220926/actions/submitEmail",
+        ]
+    );
+    assert_eq!(client.submit_endpoint(), Some(server.uri().as_str()));
+}
+
+#[tokio::test]
+async fn test_send_raw_response() {
+    const RAW: &str = r#"{"messageId":"msg-1","envelopeId":"env-1","suppressedRecipients":[{"email":"bounced@example.com"}],"extra":true}"#;
+
+    let server = start_server().await;
+    Mock::given(method("POST"))
+        .and(path("/20220926/actions/submitEmail"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(RAW, "application/json"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = email_client(&server).await;
+    let email = Email::builder()
+        .sender(EmailAddress::new("sender@example.com"))
+        .recipients(Recipients::to(vec![EmailAddress::new("to@example.com")]))
+        .subject("Hello")
+        .body_text("Hello")
+        .build()
+        .unwrap();
+
+    let (response, raw) = client.send_raw_response(&email).await.unwrap();
+    assert_eq!(raw, RAW);
+
+    let value: serde_json::Value = serde_json::from_str(&raw).unwrap();
+    assert_eq!(value["messageId"], response.message_id.as_str());
+    assert_eq!(value["envelopeId"], response.envelope_id.as_str());
+    assert_eq!(
+        value["suppressedRecipients"][0]["email"],
+        response.suppressed_recipients.unwrap()[0].email.as_str()
+    );
+}
+
+#[tokio::test]
+async fn test_send_non_json_response() {
+    let server = start_server().await;
+    Mock::given(method("POST"))
+        .and(path("/20220926/actions/submitEmail"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_raw("<html>Bad gateway</html>", "text/html"),
+        )
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = email_client(&server).await;
+    let email = Email::builder()
+        .sender(EmailAddress::new("sender@example.com"))
+        .recipients(Recipients::to(vec![EmailAddress::new("to@example.com")]))
+        .subject("Hello")
+        .body_text("Hello")
+        .build()
+        .unwrap();
+
+    // Both the parsed and the raw-response path report the start of the body
+    let send_error = client.send(email.clone()).await.unwrap_err().source;
+    let raw_error = client.send_raw_response(&email).await.unwrap_err();
+    for error in [send_error, raw_error] {
+        match error {
+            OciError::JsonResponseError { body_snippet, .. } => {
+                assert_eq!(body_snippet, "<html>Bad gateway</html>")
+            }
+            e => panic!("Expected JsonResponseError, got: {:?}", e),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_send_batch_cancelled_returns_unsent() {
+    use std::time::Duration;
+    use tokio_util::sync::CancellationToken;
+    use wiremock::matchers::body_string_contains;
+
+    let server = start_server().await;
+    // The second email hangs until the batch is cancelled
+    Mock::given(method("POST"))
+        .and(path("/20220926/actions/submitEmail"))
+        .and(body_string_contains("Second"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(60)))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/20220926/actions/submitEmail"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "messageId": "msg-1",
+            "envelopeId": "env-1",
+        })))
+        .mount(&server)
+        .await;
+
+    let client = email_client(&server).await;
+    let email = |subject: &str| {
+        Email::builder()
+            .sender(EmailAddress::new("sender@example.com"))
+            .recipients(Recipients::to(vec![EmailAddress::new("to@example.com")]))
+            .subject(subject)
+            .body_text("Hello")
+            .build()
+            .unwrap()
+    };
+    let emails = vec![email("First"), email("Second"), email("Third")];
+
+    let cancel = CancellationToken::new();
+    let canceller = {
+        let cancel = cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            cancel.cancel();
+        })
+    };
+    let (results, unsent) = client.send_batch(emails, &cancel).await;
+    canceller.await.unwrap();
+
+    // The first was sent; the in-flight second and the never-started third are handed back
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].as_ref().unwrap().message_id, "msg-1");
+    assert_eq!(
+        unsent
+            .iter()
+            .map(|e| e.subject.as_str())
+            .collect::<Vec<_>>(),
+        ["Second", "Third"]
+    );
+}
+
+#[tokio::test]
+async fn test_send_detached() {
+    let server = start_server().await;
+    Mock::given(method("POST"))
+        .and(path("/20220926/actions/submitEmail"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "messageId": "msg-1",
+            "envelopeId": "env-1",
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = email_client(&server).await;
+    let email = Email::builder()
+        .sender(EmailAddress::new("sender@example.com"))
+        .recipients(Recipients::to(vec![EmailAddress::new("to@example.com")]))
+        .subject("Hello")
+        .body_text("Hello")
+        .build()
+        .unwrap();
+
+    let handle = client.send_detached(email);
+    drop(client);
+
+    let response = handle.await.unwrap().unwrap();
+    assert_eq!(response.message_id, "msg-1");
+    assert_signed(
+        server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .find(|r| r.url.path() == "/20220926/actions/submitEmail")
+            .unwrap(),
+    );
+}
+
+#[tokio::test]
+async fn test_send_via() {
+    let server = start_server().await;
+    let secondary = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/20220926/actions/submitEmail"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "messageId": "msg-2",
+            "envelopeId": "env-2",
+        })))
+        .expect(1)
+        .mount(&secondary)
+        .await;
+
+    let client = email_client(&server).await;
+    let email = Email::builder()
+        .sender(EmailAddress::new("sender@example.com"))
+        .recipients(Recipients::to(vec![EmailAddress::new("to@example.com")]))
+        .subject("Hello")
+        .body_text("Hello")
+        .build()
+        .unwrap();
+
+    let response = client.send_via(&email, secondary.uri()).await.unwrap();
+    assert_eq!(response.message_id, "msg-2");
+
+    // Signed for the secondary host; the primary submit endpoint is untouched
+    let requests = secondary.received_requests().await.unwrap();
+    let host = secondary.uri().trim_start_matches("http://").to_string();
+    assert_eq!(header(&requests[0], "host"), host);
+    assert_signed(&requests[0]);
+    assert!(
+        server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .all(|r| r.url.path() != "/20220926/actions/submitEmail")
+    );
+    assert_eq!(client.submit_endpoint(), Some(server.uri().as_str()));
+
+    let error = client
+        .send_via(&email, "https://example.com/not/a/base")
+        .await
+        .unwrap_err();
+    assert!(matches!(error, oci_api::OciError::ConfigError(_)));
+}
+
+#[tokio::test]
+async fn test_configured_compartment_takes_precedence() {
+    let server = start_server().await;
+    let client = email_client(&server).await;
+    assert_eq!(client.compartment_id(), COMPARTMENT_ID);
+}
+
+fn header<'a>(request: &'a Request, name: &str) -> &'a str {
+    request
+        .headers
+        .get(name)
+        .unwrap_or_else(|| panic!("Missing header: {}", name))
+        .to_str()
+        .unwrap()
+}
+
+/// Verify the OCI signature of a received request with `TEST_KEY`
+fn assert_signed(request: &Request) {
+    let authorization = header(request, "authorization");
+    assert!(authorization.starts_with("Signature version=\"1\""));
+    assert!(authorization.contains(&format!(
+        "keyId=\"{}/ocid1.user.oc1..test/aa:bb:cc:dd:ee:ff\"",
+        TENANCY_ID
+    )));
+    assert!(authorization.contains("algorithm=\"rsa-sha256\""));
+
+    let request_target = match request.url.query() {
+        Some(query) => format!("{}?{}", request.url.path(), query),
+        None => request.url.path().to_string(),
+    };
+
+    let mut signing_string = format!(
+        "date: {}\n(request-target): {} {}\nhost: {}",
+        header(request, "date"),
+        request.method.as_str().to_lowercase(),
+        request_target,
+        header(request, "host"),
+    );
+
+    if authorization.contains("x-content-sha256") {
+        let body_sha256 = general_purpose::STANDARD.encode(Sha256::digest(&request.body));
+        assert_eq!(header(request, "x-content-sha256"), body_sha256);
+        assert_eq!(
+            header(request, "content-length"),
+            request.body.len().to_string()
+        );
+
+        signing_string.push_str(&format!(
+            "\ncontent-length: {}\ncontent-type: {}\nx-content-sha256: {}",
+            header(request, "content-length"),
+            header(request, "content-type"),
+            body_sha256,
+        ));
+    }
+
+    let encoded = authorization
+        .split("signature=\"")
+        .nth(1)
+        .and_then(|s| s.strip_suffix('"'))
+        .unwrap();
+    let signature = Signature::try_from(
+        general_purpose::STANDARD
+            .decode(encoded)
+            .unwrap()
+            .as_slice(),
+    )
+    .unwrap();
+
+    let key = rsa::RsaPrivateKey::from_pkcs8_pem(TEST_KEY).unwrap();
+    VerifyingKey::<Sha256>::new(key.to_public_key())
+        .verify(signing_string.as_bytes(), &signature)
+        .expect("Signature verification failed");
+}
+
+#[tokio::test]
+async fn test_get_email_configuration() {
+    let server = start_server().await;
+    let client = email_client(&server).await;
+
+    let config = client.get_email_configuration_default().await.unwrap();
+    assert_eq!(config.compartment_id, COMPARTMENT_ID);
+    let config = client
+        .get_email_configuration(COMPARTMENT_ID)
+        .await
+        .unwrap();
+    assert_eq!(config.compartment_id, COMPARTMENT_ID);
+
+    // One request from EmailClient::new (reused by the default variant), one from
+    // get_email_configuration
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 2);
+    for request in &requests {
+        assert_eq!(request.method.as_str(), "GET");
+        assert!(request.body.is_empty());
+        assert_signed(request);
+    }
+}
+
+#[tokio::test]
+async fn test_get_email_configuration_default_lazy() {
+    let server = start_server().await;
+    let oci_client = OciClient::builder(&test_config())
+        .endpoint_override("email.control", server.uri())
+        .build()
+        .unwrap();
+    let client = EmailClient::new_lazy(oci_client);
+
+    // The configuration loaded to resolve the compartment is returned as-is
+    let config = client.get_email_configuration_default().await.unwrap();
+    assert_eq!(config.compartment_id, COMPARTMENT_ID);
+    client.get_email_configuration_default().await.unwrap();
+
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_list_senders() {
+    let server = start_server().await;
+    Mock::given(method("GET"))
+        .and(path("/20170907/senders"))
+        .and(query_param("compartmentId", COMPARTMENT_ID))
+        .and(query_param("lifecycleState", "ACTIVE"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                "id": "ocid1.emailsender.oc1..test",
+                "emailAddress": "sender@example.com",
+                "lifecycleState": "ACTIVE",
+                "timeCreated": "2024-01-01T00:00:00.000Z",
+            }])),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = email_client(&server).await;
+    let senders = client
+        .list_senders_default(Some("ACTIVE"), None)
+        .await
+        .unwrap();
+    assert_eq!(senders.len(), 1);
+    assert_eq!(senders[0].email_address, "sender@example.com");
+
+    let requests = server.received_requests().await.unwrap();
+    let request = requests
+        .iter()
+        .find(|r| r.url.path() == "/20170907/senders")
+        .unwrap();
+    assert_signed(request);
+}
+
+#[tokio::test]
+async fn test_find_senders() {
+    let server = start_server().await;
+    let sender = |email: &str, is_spf: bool| {
+        serde_json::json!({
+            "id": format!("ocid1.emailsender.oc1..{}", email),
+            "emailAddress": email,
+            "lifecycleState": "ACTIVE",
+            "timeCreated": "2024-01-01T00:00:00.000Z",
+            "isSpf": is_spf,
+        })
+    };
+    Mock::given(method("GET"))
+        .and(path("/20170907/senders"))
+        .and(query_param("compartmentId", COMPARTMENT_ID))
+        .and(query_param("lifecycleState", "ACTIVE"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            sender("spf@example.com", true),
+            sender("nospf@example.com", false),
+            sender("spf@other.com", true),
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = email_client(&server).await;
+    let filter = SenderFilter::new()
+        .state(SenderLifecycleState::Active)
+        .spf_required(true)
+        .domain("example.com");
+    let senders = client.find_senders(COMPARTMENT_ID, &filter).await.unwrap();
+
+    assert_eq!(senders.len(), 1);
+    assert_eq!(senders[0].email_address, "spf@example.com");
+}
+
+#[tokio::test]
+async fn test_send() {
+    let server = start_server().await;
+    Mock::given(method("POST"))
+        .and(path("/20220926/actions/submitEmail"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "messageId": "msg-1",
+            "envelopeId": "env-1",
+            "suppressedRecipients": [],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = email_client(&server).await;
+    let email = Email::builder()
+        .sender(EmailAddress::new("sender@example.com"))
+        .recipients(Recipients::to(vec![EmailAddress::new("to@example.com")]))
+        .subject("Hello")
+        .body_text("Hello from the mock test")
+        .build()
+        .unwrap();
+
+    let response = client.send(email).await.unwrap();
+    assert_eq!(response.message_id, "msg-1");
+
+    let requests = server.received_requests().await.unwrap();
+    let request = requests
+        .iter()
+        .find(|r| r.url.path() == "/20220926/actions/submitEmail")
+        .unwrap();
+    assert_eq!(
+        header(request, "content-type"),
+        "application/json; charset=utf-8"
+    );
+    assert_signed(request);
+
+    let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(body["sender"]["compartmentId"], COMPARTMENT_ID);
+    assert_eq!(
+        body["sender"]["senderAddress"]["email"],
+        "sender@example.com"
+    );
+    assert_eq!(body["recipients"]["to"][0]["email"], "to@example.com");
+    assert_eq!(body["subject"], "Hello");
+    assert_eq!(body["bodyText"], "Hello from the mock test");
+}
+
+#[tokio::test]
+async fn test_send_in_compartment() {
+    const OVERRIDE_ID: &str = "ocid1.compartment.oc1..override";
+
+    let server = start_server().await;
+    Mock::given(method("POST"))
+        .and(path("/20220926/actions/submitEmail"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "messageId": "msg-1",
+            "envelopeId": "env-1",
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = email_client(&server).await;
+    let email = Email::builder()
+        .sender(EmailAddress::new("sender@example.com"))
+        .recipients(Recipients::to(vec![EmailAddress::new("to@example.com")]))
+        .subject("Hello")
+        .body_text("Hello")
+        .build()
+        .unwrap();
+
+    let response = client
+        .send_in_compartment(email, OVERRIDE_ID)
+        .await
+        .unwrap();
+    assert_eq!(response.message_id, "msg-1");
+    assert_eq!(client.compartment_id(), COMPARTMENT_ID);
+
+    let requests = server.received_requests().await.unwrap();
+    let request = requests
+        .iter()
+        .find(|r| r.url.path() == "/20220926/actions/submitEmail")
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(body["sender"]["compartmentId"], OVERRIDE_ID);
+}
+
+#[tokio::test]
+async fn test_send_multibyte_subject_content_type() {
+    let server = start_server().await;
+    Mock::given(method("POST"))
+        .and(path("/20220926/actions/submitEmail"))
+        .and(wiremock::matchers::header(
+            "content-type",
+            "application/json; charset=utf-8",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "messageId": "msg-1",
+            "envelopeId": "env-1",
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = email_client(&server).await;
+    let email = Email::builder()
+        .sender(EmailAddress::with_name("sender@example.com", "보내는 사람"))
+        .recipients(Recipients::to(vec![EmailAddress::new("to@example.com")]))
+        .subject("주문 확인 — Ünïcödé ✉")
+        .body_text("안녕하세요")
+        .build()
+        .unwrap();
+
+    let response = client.send(email).await.unwrap();
+    assert_eq!(response.message_id, "msg-1");
+
+    let requests = server.received_requests().await.unwrap();
+    let request = requests
+        .iter()
+        .find(|r| r.url.path() == "/20220926/actions/submitEmail")
+        .unwrap();
+
+    // The signature covers the content-type that was sent, and the length in bytes
+    let authorization = header(request, "authorization");
+    assert!(authorization.contains("content-type"));
+    assert_signed(request);
+    assert_eq!(
+        header(request, "content-length"),
+        request.body.len().to_string()
+    );
+
+    let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(body["subject"], "주문 확인 — Ünïcödé ✉");
+    assert_eq!(body["bodyText"], "안녕하세요");
+}
+
+#[tokio::test]
+async fn test_list_senders_exact_match() {
+    let server = start_server().await;
+    let sender = |address: &str| {
+        serde_json::json!({
+            "id": format!("ocid1.emailsender.oc1..{}", address),
+            "emailAddress": address,
+            "lifecycleState": "ACTIVE",
+            "timeCreated": "2024-01-01T00:00:00.000Z",
+        })
+    };
+    // Seeded as if the server did prefix matching
+    Mock::given(method("GET"))
+        .and(path("/20170907/senders"))
+        .and(query_param("emailAddress", "info@example.com"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            sender("info@example.com"),
+            sender("info@example.com.evil"),
+            sender("Info@Example.com"),
+            sender("info@example.co"),
+        ])))
+        .mount(&server)
+        .await;
+
+    let client = email_client(&server).await;
+
+    let senders = client
+        .list_senders_default(None, Some("info@example.com"))
+        .await
+        .unwrap();
+    assert_eq!(senders.len(), 4);
+
+    let senders = client
+        .list_senders_exact(client.compartment_id(), None, "info@example.com")
+        .await
+        .unwrap();
+    let addresses: Vec<&str> = senders.iter().map(|s| s.email_address.as_str()).collect();
+    assert_eq!(addresses, ["info@example.com", "Info@Example.com"]);
+}
+
+#[tokio::test]
+async fn test_send_converts_international_domains() {
+    let server = start_server().await;
+    Mock::given(method("POST"))
+        .and(path("/20220926/actions/submitEmail"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "messageId": "msg-1",
+            "envelopeId": "env-1",
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = email_client(&server).await;
+    let email = Email::builder()
+        .sender(EmailAddress::new("sender@example.com"))
+        .recipients(Recipients::to(vec![
+            EmailAddress::with_name("user@müller.de", "Jürgen Müller"),
+            EmailAddress::new("ascii@example.com"),
+        ]))
+        .subject("Hello")
+        .body_text("Hello")
+        .build()
+        .unwrap();
+
+    client.send_ref(&email, None).await.unwrap();
+    client.send(email).await.unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    let submits = requests
+        .iter()
+        .filter(|r| r.url.path() == "/20220926/actions/submitEmail");
+    for request in submits {
+        let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(
+            body["recipients"]["to"],
+            serde_json::json!([
+                {"email": "user@xn--mller-kva.de", "name": "Jürgen Müller"},
+                {"email": "ascii@example.com"},
+            ])
+        );
+        assert_eq!(
+            body["sender"]["senderAddress"]["email"],
+            "sender@example.com"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_send_ref_twice() {
+    let server = start_server().await;
+    Mock::given(method("POST"))
+        .and(path("/20220926/actions/submitEmail"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "messageId": "msg-1",
+            "envelopeId": "env-1",
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = email_client(&server).await;
+    let email = email_from("sender@example.com");
+
+    client.send_ref(&email, None).await.unwrap();
+    client
+        .send_ref(&email, Some("ocid1.compartment.oc1..other"))
+        .await
+        .unwrap();
+
+    // The caller's email is left untouched
+    assert!(email.sender.compartment_id.is_empty());
+
+    let compartments: Vec<serde_json::Value> = server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .filter(|r| r.url.path() == "/20220926/actions/submitEmail")
+        .map(|r| {
+            assert_signed(r);
+            let body: serde_json::Value = serde_json::from_slice(&r.body).unwrap();
+            assert_eq!(body["subject"], "Hello");
+            body["sender"]["compartmentId"].clone()
+        })
+        .collect();
+    assert_eq!(
+        compartments,
+        [
+            serde_json::json!(COMPARTMENT_ID),
+            serde_json::json!("ocid1.compartment.oc1..other")
+        ]
+    );
+
+    assert!(matches!(
+        client.send_ref(&email, Some("invalid")).await,
+        Err(oci_api::OciError::ConfigError(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_send_detailed() {
+    let server = start_server().await;
+    Mock::given(method("POST"))
+        .and(path("/20220926/actions/submitEmail"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "messageId": "msg-1",
+            "envelopeId": "env-1",
+            "suppressedRecipients": [{ "email": "bounced@example.com" }],
+        })))
+        .mount(&server)
+        .await;
+
+    let client = email_client(&server).await;
+    let email = Email::builder()
+        .sender(EmailAddress::new("sender@example.com"))
+        .recipients(Recipients::to(vec![
+            EmailAddress::new("to@example.com"),
+            EmailAddress::new("bounced@example.com"),
+        ]))
+        .subject("Hello")
+        .body_text("Hello")
+        .build()
+        .unwrap();
+
+    let outcome = client.send_detailed(email).await.unwrap();
+    assert_eq!(outcome.accepted, vec![EmailAddress::new("to@example.com")]);
+    assert_eq!(
+        outcome.suppressed,
+        vec![EmailAddress::new("bounced@example.com")]
+    );
+    assert!(outcome.is_partial());
+}
+
+#[tokio::test]
+async fn test_send_raw_with_unmodeled_field() {
+    let server = start_server().await;
+    Mock::given(method("POST"))
+        .and(path("/20220926/actions/submitEmail"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "messageId": "msg-raw",
+            "envelopeId": "env-raw",
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = email_client(&server).await;
+    let response = client
+        .send_raw(serde_json::json!({
+            "sender": { "senderAddress": { "email": "sender@example.com" } },
+            "recipients": { "to": [{ "email": "to@example.com" }] },
+            "subject": "Raw",
+            "bodyText": "Raw body",
+            "futureField": { "enabled": true },
+        }))
+        .await
+        .unwrap();
+    assert_eq!(response.message_id, "msg-raw");
+
+    let requests = server.received_requests().await.unwrap();
+    let request = requests
+        .iter()
+        .find(|r| r.url.path() == "/20220926/actions/submitEmail")
+        .unwrap();
+    assert_signed(request);
+
+    let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(body["futureField"]["enabled"], true);
+    assert_eq!(body["sender"]["compartmentId"], COMPARTMENT_ID);
+}
+
+#[tokio::test]
+async fn test_hooks_fire_for_send() {
+    use std::sync::{Arc, Mutex};
+
+    let server = start_server().await;
+    Mock::given(method("POST"))
+        .and(path("/20220926/actions/submitEmail"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "messageId": "msg-1",
+            "envelopeId": "env-1",
+        })))
+        .mount(&server)
+        .await;
+
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let responses = Arc::new(Mutex::new(Vec::new()));
+    let (requests_hook, responses_hook) = (requests.clone(), responses.clone());
+
+    let oci_client = OciClient::builder(&test_config())
+        .endpoint_override("email.control", server.uri())
+        .endpoint_override("email.submit", server.uri())
+        .on_request(move |info| {
+            requests_hook
+                .lock()
+                .unwrap()
+                .push(format!("{} {}", info.method, info.path));
+        })
+        .on_response(move |info| {
+            responses_hook.lock().unwrap().push((
+                info.path.to_string(),
+                info.status.map(|s| s.as_u16()),
+                info.elapsed.is_some(),
+            ));
+        })
+        .build()
+        .unwrap();
+    let client = EmailClient::new(oci_client).await.unwrap();
+
+    let email = Email::builder()
+        .sender(EmailAddress::new("sender@example.com"))
+        .recipients(Recipients::to(vec![EmailAddress::new("to@example.com")]))
+        .subject("Hello")
+        .body_text("Hello")
+        .build()
+        .unwrap();
+    client.send(email).await.unwrap();
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1], "POST /20220926/actions/submitEmail");
+
+    let responses = responses.lock().unwrap();
+    assert_eq!(
+        responses[1],
+        ("/20220926/actions/submitEmail".to_string(), Some(200), true)
+    );
+}
+
+/// Mount a list senders mock returning `senders` for `address`
+async fn mount_senders(server: &MockServer, address: &str, senders: serde_json::Value) {
+    Mock::given(method("GET"))
+        .and(path("/20170907/senders"))
+        .and(query_param("lifecycleState", "ACTIVE"))
+        .and(query_param("emailAddress", address))
+        .respond_with(ResponseTemplate::new(200).set_body_json(senders))
+        .mount(server)
+        .await;
+}
+
+fn email_from(sender: &str) -> Email {
+    Email::builder()
+        .sender(EmailAddress::new(sender))
+        .recipients(Recipients::to(vec![EmailAddress::new("to@example.com")]))
+        .subject("Hello")
+        .body_text("Hello")
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_validate_reports_all_issues() {
+    let server = start_server().await;
+    mount_senders(&server, "sender@example.com", serde_json::json!([])).await;
+
+    let client = email_client(&server).await;
+    let email = Email {
+        message_id: None,
+        sender: Sender::new("sender@example.com"),
+        recipients: Recipients {
+            to: Some(vec![EmailAddress::new("not-an-address")]),
+            cc: Some(vec![EmailAddress::new("cc@example.com")]),
+            bcc: Some(vec![EmailAddress::new("bcc@@example")]),
+        },
+        subject: " ".to_string(),
+        body_html: None,
+        body_text: None,
+        reply_to: Some(vec![EmailAddress::new("reply@example")]),
+        headers: None,
+    };
+
+    let issues = client.validate(&email).await;
+    assert_eq!(
+        issues,
+        [
+            ValidationIssue::InvalidAddress {
+                field: "to",
+                address: "not-an-address".to_string()
+            },
+            ValidationIssue::InvalidAddress {
+                field: "bcc",
+                address: "bcc@@example".to_string()
+            },
+            ValidationIssue::InvalidAddress {
+                field: "replyTo",
+                address: "reply@example".to_string()
+            },
+            ValidationIssue::EmptySubject,
+            ValidationIssue::MissingBody,
+            ValidationIssue::SenderNotApproved {
+                address: "sender@example.com".to_string(),
+                compartment_id: COMPARTMENT_ID.to_string()
+            },
+        ]
+    );
+
+    // Nothing is sent
+    let result = client.submit_validated(email).await;
+    match result.unwrap_err() {
+        oci_api::OciError::ConfigError(message) => {
+            assert!(message.starts_with("Email validation failed: "));
+            assert!(message.contains("Invalid to address: 'not-an-address'"));
+            assert!(message.contains("Subject is empty"));
+            assert!(message.contains("is not an active approved sender"));
+        }
+        e => panic!("Expected ConfigError, got: {:?}", e),
+    }
+    let requests = server.received_requests().await.unwrap();
+    assert!(
+        requests
+            .iter()
+            .all(|r| r.url.path() != "/20220926/actions/submitEmail")
+    );
+}
+
+#[tokio::test]
+async fn test_validate_invalid_compartment_skips_sender_check() {
+    let server = start_server().await;
+    let client = email_client(&server).await;
+
+    let mut email = email_from("sender@example.com");
+    email.sender.compartment_id = "not-an-ocid".to_string();
+    email.recipients = Recipients::to(Vec::new());
+
+    assert_eq!(
+        client.validate(&email).await,
+        [
+            ValidationIssue::NoRecipients,
+            ValidationIssue::InvalidCompartment("not-an-ocid".to_string()),
+        ]
+    );
+    // No list_senders request
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_submit_validated_sends_clean_email() {
+    let server = start_server().await;
+    mount_senders(
+        &server,
+        "sender@example.com",
+        serde_json::json!([{
+            "id": "ocid1.emailsender.oc1..test",
+            "emailAddress": "sender@example.com",
+            "lifecycleState": "ACTIVE",
+            "timeCreated": "2024-01-01T00:00:00.000Z",
+        }]),
+    )
+    .await;
+    Mock::given(method("POST"))
+        .and(path("/20220926/actions/submitEmail"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "messageId": "msg-1",
+            "envelopeId": "env-1",
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = email_client(&server).await;
+    let email = email_from("sender@example.com");
+    assert!(client.validate(&email).await.is_empty());
+
+    let response = client.submit_validated(email).await.unwrap();
+    assert_eq!(response.message_id, "msg-1");
+}
+
+#[tokio::test]
+async fn test_verify_sender_approved() {
+    let server = start_server().await;
+    mount_senders(
+        &server,
+        "sender@example.com",
+        serde_json::json!([{
+            "id": "ocid1.emailsender.oc1..test",
+            "emailAddress": "sender@example.com",
+            "lifecycleState": "ACTIVE",
+            "timeCreated": "2024-01-01T00:00:00.000Z",
+        }]),
+    )
+    .await;
+    Mock::given(method("POST"))
+        .and(path("/20220926/actions/submitEmail"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "messageId": "msg-1",
+            "envelopeId": "env-1",
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = email_client(&server).await.with_sender_verification(true);
+    client
+        .verify_sender_approved(&email_from("sender@example.com"))
+        .await
+        .unwrap();
+    client.send(email_from("sender@example.com")).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_verify_sender_not_approved() {
+    let server = start_server().await;
+    mount_senders(&server, "unknown@example.com", serde_json::json!([])).await;
+    Mock::given(method("POST"))
+        .and(path("/20220926/actions/submitEmail"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = email_client(&server).await.with_sender_verification(true);
+    match client
+        .send(email_from("unknown@example.com"))
+        .await
+        .unwrap_err()
+        .source
+    {
+        oci_api::OciError::ConfigError(msg) => {
+            // Identifiers are masked in the message
+            assert!(msg.contains("'u***@example.com'"), "{msg}");
+            assert!(msg.contains("'ocid1.compartment.oc1..***'"), "{msg}");
+            assert!(!msg.contains("unknown@example.com"));
+        }
+        e => panic!("Expected ConfigError, got: {:?}", e),
+    }
+}
+
+#[tokio::test]
+async fn test_get_and_delete_sender_with_etag() {
+    let server = start_server().await;
+    Mock::given(method("GET"))
+        .and(path("/20170907/senders/ocid1.emailsender.oc1..test"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("etag", "etag-1")
+                .set_body_json(serde_json::json!({
+                    "id": "ocid1.emailsender.oc1..test",
+                    "emailAddress": "sender@example.com",
+                    "lifecycleState": "ACTIVE",
+                    "timeCreated": "2024-01-01T00:00:00.000Z",
+                })),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/20170907/senders/ocid1.emailsender.oc1..test"))
+        .and(wiremock::matchers::header("if-match", "etag-1"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/20170907/senders/ocid1.emailsender.oc1..test"))
+        .and(wiremock::matchers::header("if-match", "stale"))
+        .respond_with(
+            ResponseTemplate::new(412)
+                .insert_header("opc-request-id", "precondition-1")
+                .set_body_json(serde_json::json!({
+                    "code": "NoEtagMatch",
+                    "message": "The resource has changed",
+                })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = email_client(&server).await;
+    let sender = client
+        .get_sender("ocid1.emailsender.oc1..test")
+        .await
+        .unwrap();
+    assert_eq!(sender.etag.as_deref(), Some("etag-1"));
+
+    match client
+        .delete_sender(&sender.id, Some("stale"))
+        .await
+        .unwrap_err()
+    {
+        oci_api::OciError::PreconditionFailed { message, trace, .. } => {
+            assert_eq!(message, "The resource has changed");
+            assert_eq!(trace.unwrap().opc_request_ids, ["precondition-1"]);
+        }
+        e => panic!("Expected PreconditionFailed, got: {:?}", e),
+    }
+
+    client
+        .delete_sender(&sender.id, sender.etag.as_deref())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_create_sender() {
+    let server = start_server().await;
+    Mock::given(method("POST"))
+        .and(path("/20170907/senders"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "ocid1.emailsender.oc1..new",
+            "emailAddress": "new@example.com",
+            "lifecycleState": "CREATING",
+            "timeCreated": "2024-01-01T00:00:00.000Z",
+            "compartmentId": COMPARTMENT_ID,
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = email_client(&server).await;
+    let sender = client
+        .create_sender(COMPARTMENT_ID, "new@example.com")
+        .await
+        .unwrap();
+    assert_eq!(sender.id, "ocid1.emailsender.oc1..new");
+
+    let requests = server.received_requests().await.unwrap();
+    let request = requests
+        .iter()
+        .find(|r| r.method.as_str() == "POST")
+        .unwrap();
+    assert_signed(request);
+    let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+    assert_eq!(body["compartmentId"], COMPARTMENT_ID);
+    assert_eq!(body["emailAddress"], "new@example.com");
+}
+
+#[tokio::test]
+async fn test_api_error() {
+    let server = start_server().await;
+    Mock::given(method("POST"))
+        .and(path("/20220926/actions/submitEmail"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "code": "InvalidParameter",
+            "message": "Sender is not approved",
+        })))
+        .mount(&server)
+        .await;
+
+    let client = email_client(&server).await;
+    let email = Email::builder()
+        .sender(EmailAddress::new("unknown@example.com"))
+        .recipients(Recipients::to(vec![EmailAddress::new("to@example.com")]))
+        .subject("Hello")
+        .body_text("Hello")
+        .build()
+        .unwrap();
+
+    let error = client.send(email.clone()).await.unwrap_err();
+    match &error.source {
+        oci_api::OciError::ApiError { code, message, .. } => {
+            assert!(code.starts_with("400"));
+            assert_eq!(message, "Sender is not approved");
+        }
+        e => panic!("Expected ApiError, got: {:?}", e),
+    }
+
+    // The email is handed back unchanged for a retry
+    let (returned, _) = error.into_parts();
+    assert_eq!(returned, email);
+    assert!(returned.sender.compartment_id.is_empty());
+}
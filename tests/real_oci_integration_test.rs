@@ -122,22 +122,21 @@ async fn test_send_full_flow() {
         return;
     }
 
-    // Skip this test - it requires approved sender email
-    // Use test_send_with_real_sender instead
-    eprintln!(
-        "Skipping test: This test requires TEST_SENDER_EMAIL environment variable with an approved sender."
-    );
-    eprintln!("Use test_send_with_real_sender instead, which auto-detects approved senders.");
-    return;
-
-    // Get sender and recipient from env or use defaults for testing
-    let test_sender =
-        std::env::var("TEST_SENDER_EMAIL").unwrap_or_else(|_| "sender@example.com".to_string());
+    // This test requires an approved sender email
+    // Use test_send_with_real_sender to auto-detect one instead
+    let Ok(test_sender) = std::env::var("TEST_SENDER_EMAIL") else {
+        eprintln!(
+            "Skipping test: This test requires TEST_SENDER_EMAIL environment variable with an approved sender."
+        );
+        eprintln!("Use test_send_with_real_sender instead, which auto-detects approved senders.");
+        return;
+    };
+
+    // Get recipient from env or use a default for testing
     let test_recipient = std::env::var("TEST_RECIPIENT_EMAIL")
         .unwrap_or_else(|_| "recipient@example.com".to_string());
 
     let config = OciConfig::from_env().expect("Failed to load config");
-    let tenancy_id = config.tenancy_id.clone();
     let oci_client = OciClient::new(&config).expect("Failed to create OCI client");
     let email_client = EmailClient::new(oci_client)
         .await